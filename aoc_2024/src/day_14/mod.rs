@@ -1,5 +1,6 @@
 mod math;
-use math::{crt, stats};
+use common::prelude::*;
+use math::stats;
 trait New
 where
     Self: Sized,
@@ -89,8 +90,23 @@ impl Robot {
     }
 
     fn next(&mut self, tile_size: &Position) {
-        self.position.0 = (self.position.0 + self.velocity.0).rem_euclid(tile_size.0);
-        self.position.1 = (self.position.1 + self.velocity.1).rem_euclid(tile_size.1);
+        self.advance(1, tile_size);
+    }
+
+    /// Moves `steps` times at once, wrapping around the edges of the room.
+    fn advance(&mut self, steps: i32, tile_size: &Position) {
+        // Widened so that any number of steps moves without overflowing before the wrap.
+        let along = |position: i32, velocity: i32| {
+            i64::from(position) + i64::from(velocity) * i64::from(steps)
+        };
+        let moved = Point::new(
+            along(self.position.0, self.velocity.0),
+            along(self.position.1, self.velocity.1),
+        );
+        let wrapped = moved
+            .wrapped_as(&Point::new(tile_size.0, tile_size.1))
+            .expect("room should not be empty");
+        self.position = Position(wrapped.x, wrapped.y);
     }
 }
 
@@ -110,17 +126,11 @@ impl Room {
         }
     }
 
-    fn move_robots(&mut self) {
-        for robot in self.robots.iter_mut() {
-            robot.next(&self.tile_size);
-        }
-        self.iterations += 1;
-    }
-
     fn move_robots_n_times(&mut self, n: i32) {
-        for _ in 0..n {
-            self.move_robots();
+        for robot in self.robots.iter_mut() {
+            robot.advance(n, &self.tile_size);
         }
+        self.iterations += n;
     }
 
     fn get_output(&mut self) -> i64 {
@@ -182,7 +192,7 @@ impl Room {
             .min_by(|a, b| a.1.partial_cmp(b.1).unwrap())
             .map(|(idx, _)| idx as i32 + 1)?;
 
-        crt::solve(min_x_variance, rows, min_y_variance, columns)
+        crt([(min_x_variance, rows), (min_y_variance, columns)]).map(|(time, _)| time)
     }
 }
 
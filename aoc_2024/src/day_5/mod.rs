@@ -1,4 +1,5 @@
 use ahash::AHashSet as HashSet;
+use common::prelude::Graph;
 // use std::collections::HashSet;
 
 fn parse(input: &str) -> (HashSet<(i32, i32)>, Vec<Vec<i32>>) {
@@ -29,14 +30,23 @@ fn parse(input: &str) -> (HashSet<(i32, i32)>, Vec<Vec<i32>>) {
 fn sorted(x: &i32, y: &i32, rules: &HashSet<(i32, i32)>) -> bool {
     !rules.contains(&(*y, *x))
 }
-fn compare(x: &i32, y: &i32, rules: &HashSet<(i32, i32)>) -> std::cmp::Ordering {
-    if rules.contains(&(*x, *y)) {
-        std::cmp::Ordering::Less
-    } else if rules.contains(&(*y, *x)) {
-        std::cmp::Ordering::Greater
-    } else {
-        std::cmp::Ordering::Equal
+/// Orders the pages using only the rules that mention two of them.
+fn ordered(pages: &[i32], rules: &HashSet<(i32, i32)>) -> Vec<i32> {
+    let mut graph = Graph::new();
+    for &page in pages {
+        graph.add_node(page);
     }
+    for &(before, after) in rules {
+        if graph.id(&before).is_some() && graph.id(&after).is_some() {
+            graph.add_edge(before, after);
+        }
+    }
+    graph
+        .topological_sort()
+        .expect("rules should not contradict each other")
+        .into_iter()
+        .map(|id| *graph.key(id).unwrap())
+        .collect()
 }
 
 #[inline]
@@ -62,10 +72,7 @@ pub fn part2(input: &str) -> i32 {
     pages_to_produce
         .into_iter()
         .filter(|pages| !pages.is_sorted_by(|a, b| sorted(a, b, &rules)))
-        .map(|mut pages| {
-            pages.sort_by(|a, b| compare(a, b, &rules));
-            pages[pages.len() / 2]
-        })
+        .map(|pages| ordered(&pages, &rules)[pages.len() / 2])
         .sum()
 }
 
@@ -1,4 +1,5 @@
 use ahash::AHashSet as HashSet;
+use common::prelude::{CardinalDirections, DirectionBehaviour, Point};
 //use std::collections::HashSet;
 use std::char;
 use std::sync::mpsc;
@@ -9,52 +10,14 @@ enum Finish {
     OutOfBounds,
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
-enum Direction {
-    Up,
-    Down,
-    Left,
-    Right,
-}
-
-impl Direction {
-    fn from_char(c: char) -> Option<Direction> {
-        match c {
-            '^' => Some(Direction::Up),
-            'v' => Some(Direction::Down),
-            '<' => Some(Direction::Left),
-            '>' => Some(Direction::Right),
-            _ => None,
-        }
-    }
-
-    fn next(&mut self) -> Self {
-        match self {
-            Direction::Up => Direction::Right,
-            Direction::Right => Direction::Down,
-            Direction::Down => Direction::Left,
-            Direction::Left => Direction::Up,
-        }
-    }
-
-    fn get_next_pos(&self, (i, j): (usize, usize)) -> (Option<usize>, Option<usize>) {
-        match self {
-            Direction::Up => (i.checked_sub(1), Some(j)),
-            Direction::Down => (i.checked_add(1), Some(j)),
-            Direction::Left => (Some(i), j.checked_sub(1)),
-            Direction::Right => (Some(i), j.checked_add(1)),
-        }
-    }
-}
-
 struct Guard {
     pos: (usize, usize),
-    dir: Direction,
-    visited: HashSet<((usize, usize), Direction)>,
+    dir: CardinalDirections,
+    visited: HashSet<((usize, usize), CardinalDirections)>,
 }
 
 impl Guard {
-    fn new(pos: (usize, usize), dir: Direction) -> Self {
+    fn new(pos: (usize, usize), dir: CardinalDirections) -> Self {
         Guard {
             pos,
             dir,
@@ -74,7 +37,11 @@ impl Guard {
         self.visited.insert((self.pos, self.dir));
         // check if we've visited this position before, if so infinite loop
         // get next position
-        let (Some(next_i), Some(next_j)) = self.dir.get_next_pos((i, j)) else {
+        let Some(Point {
+            x: next_i,
+            y: next_j,
+        }) = self.dir.next_point(&Point::new(i, j))
+        else {
             return Finish::OutOfBounds;
         };
 
@@ -83,7 +50,7 @@ impl Guard {
         };
         // check if it's a wall
         if *char == '#' {
-            self.dir = self.dir.next();
+            self.dir = self.dir.rotate_clockwise();
         } else {
             self.pos = (next_i, next_j);
         }
@@ -102,7 +69,7 @@ impl Guard {
 fn find_start(grid: &[Vec<char>]) -> Option<Guard> {
     for (i, v) in grid.iter().enumerate() {
         for (j, c) in v.iter().enumerate() {
-            if let Some(d) = Direction::from_char(*c) {
+            if let Some(d) = CardinalDirections::from_arrow(*c) {
                 return Some(Guard::new((i, j), d));
             }
         }
@@ -141,7 +108,7 @@ pub fn part2(input: &str) -> i32 {
         } else {
             new_grid[*i][*j] = '#';
         }
-        let mut new_guard = Guard::new(start, Direction::Up);
+        let mut new_guard = Guard::new(start, CardinalDirections::North);
 
         let tx = tx.clone();
 
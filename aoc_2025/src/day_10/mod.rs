@@ -5,7 +5,7 @@ use std::{
     sync::RwLock,
 };
 
-use itertools::Itertools;
+use common::prelude::*;
 use rayon::iter::ParallelIterator;
 use rayon::iter::{IntoParallelIterator, IntoParallelRefIterator};
 #[derive(Copy, Clone, Debug, Hash, PartialEq, Eq)]
@@ -21,11 +21,6 @@ impl Light {
         Light(bits)
     }
 
-    fn from_buttons(buttons: &[&Button]) -> Self {
-        let bits = buttons.iter().fold(0u16, |acc, &n| acc ^ **n);
-        Light(bits)
-    }
-
     fn from_joltage(joltages: &[u16]) -> Self {
         let bits =
             joltages.iter().enumerate().fold(
@@ -197,21 +192,24 @@ fn parse(input: &str) -> Vec<(Light, Buttons, Joltage)> {
         .collect()
 }
 
-fn fewest_buttons(light: &Light, buttons: &[Button]) -> usize {
-    buttons
+/// Solves which buttons toggle `light` on as a system over GF(2), one unknown per button.
+fn xor_solution(light: &Light, buttons: &[Button]) -> Gf2Solution {
+    let bits = |mask: u16| BitVector::from_indices(16, (0..16).filter(|i| (mask >> i) & 1 == 1));
+    let columns = buttons
         .iter()
-        .powerset()
-        // .par_bridge()
-        .filter_map(|pressed: Vec<&Button>| {
-            let on = Light::from_buttons(pressed.as_ref());
-            if on == *light {
-                Some(pressed.len())
-            } else {
-                None
-            }
-        })
-        .min()
-        .unwrap()
+        .map(|button| bits(**button))
+        .collect::<Vec<_>>();
+    Gf2System::from_columns(&columns, &bits(light.0))
+        .expect("every button covers the same 16 lights")
+        .solve()
+}
+
+fn fewest_buttons(light: &Light, buttons: &[Button]) -> usize {
+    xor_solution(light, buttons)
+        .min_weight()
+        .expect("machines should have few buttons")
+        .expect("lights should be reachable")
+        .count_ones()
 }
 
 #[inline]
@@ -225,17 +223,9 @@ pub fn part1(input: &str) -> u32 {
 }
 
 fn valid_combination(light: &Light, buttons: &Buttons) -> Vec<Buttons> {
-    buttons
-        .iter()
-        .powerset()
-        .filter_map(|pressed| {
-            let on = Light::from_buttons(pressed.as_ref());
-            if on == *light {
-                Some(Buttons(pressed.into_iter().copied().collect()))
-            } else {
-                None
-            }
-        })
+    xor_solution(light, buttons)
+        .solutions()
+        .map(|pressed| Buttons(pressed.iter_ones().map(|idx| buttons[idx]).collect()))
         .collect()
 }
 // Recursive function that takes in a joltage and a set of buttons and returns the number of buttons pressed to solve the puzzle
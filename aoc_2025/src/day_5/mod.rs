@@ -1,23 +1,9 @@
-use std::collections::VecDeque;
+use common::prelude::RangeSet;
 
-fn parse(
-    input: &str,
-) -> (
-    impl Iterator<Item = u64> + '_,
-    impl Iterator<Item = (u64, u64)> + '_,
-) {
-    let split = input.find("\n\n").unwrap() + '\n'.len_utf8();
+fn parse(input: &str) -> (impl Iterator<Item = u64> + '_, RangeSet<u64>) {
+    let (ranges, ids) = input.split_once("\n\n").unwrap();
 
-    let (ranges, ids) = input.split_at(split);
-
-    let ids = &ids['\n'.len_utf8()..]; // remove leading newline
-
-    let ranges = ranges.lines().map(|line| {
-        let mut parts = line.split('-');
-        let start = parts.next().unwrap().parse().unwrap();
-        let end = parts.next().unwrap().parse().unwrap();
-        (start, end)
-    });
+    let ranges = ranges.parse().unwrap();
     let ids = ids.lines().map(|line| line.parse().unwrap());
     (ids, ranges)
 }
@@ -25,41 +11,17 @@ fn parse(
 #[inline]
 pub fn part1(input: &str) -> u64 {
     let (ids, ranges) = parse(input);
-    let ranges = ranges.collect::<Vec<_>>();
 
-    ids.filter(|&id| ranges.iter().any(|&(start, end)| id >= start && id <= end))
-        .count() as u64
+    ids.filter(|&id| ranges.contains(id)).count() as u64
 }
 
 #[inline]
 pub fn part2(input: &str) -> u64 {
     let (_ids, ranges) = parse(input);
 
-    let mut ranges = ranges.collect::<Vec<_>>();
-
-    ranges.sort_unstable_by(|(a_start, a_end), (b_start, b_end)| {
-        a_start.cmp(b_start).then(a_end.cmp(b_end))
-    });
-    let mut ranges = ranges.into_iter().collect::<VecDeque<_>>();
-
-    let mut new_ranges: Vec<(u64, u64)> = vec![];
-    let (mut start, mut end) = ranges.pop_front().unwrap();
-
-    while let Some((next_start, next_end)) = ranges.pop_front() {
-        // extend range
-        if next_start <= end + 1 {
-            end = end.max(next_end);
-        }
-        // complete range
-        else {
-            new_ranges.push((start, end));
-            start = next_start;
-            end = next_end;
-        }
-    }
-    new_ranges.push((start, end));
-
-    new_ranges.iter().map(|(start, end)| 1 + end - start).sum()
+    ranges
+        .len()
+        .expect("fresh ingredient count should fit in a u64")
 }
 
 common::aoc_test!(3, 529, 14, 344260049617193);
@@ -0,0 +1,122 @@
+/// Cycle detection for simulations whose state eventually repeats.
+/// Responsibilities:
+/// - Brent's and Floyd's constant-memory detection
+/// - Hash-based detection that keeps every state seen
+/// - Mapping an arbitrary future step onto the cycle
+use std::collections::HashMap;
+use std::hash::Hash;
+
+/// A cycle in a sequence of states. The state at step `start + length` equals the state at step `start`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Cycle {
+    /// The first step that is part of the cycle.
+    pub start: usize,
+    pub length: usize,
+}
+
+impl Cycle {
+    /// Brent's algorithm. Uses constant memory and fewer steps than Floyd's.
+    pub fn brent<S: Clone + Eq>(initial: &S, step: impl Fn(&S) -> S) -> Self {
+        let mut power = 1;
+        let mut length = 1;
+        let mut tortoise = initial.clone();
+        let mut hare = step(initial);
+        while tortoise != hare {
+            if power == length {
+                tortoise = hare.clone();
+                power *= 2;
+                length = 0;
+            }
+            hare = step(&hare);
+            length += 1;
+        }
+
+        let mut tortoise = initial.clone();
+        let mut hare = (0..length).fold(initial.clone(), |state, _| step(&state));
+        let mut start = 0;
+        while tortoise != hare {
+            tortoise = step(&tortoise);
+            hare = step(&hare);
+            start += 1;
+        }
+        Self { start, length }
+    }
+
+    /// Floyd's tortoise and hare. Uses constant memory.
+    pub fn floyd<S: Clone + Eq>(initial: &S, step: impl Fn(&S) -> S) -> Self {
+        let mut tortoise = step(initial);
+        let mut hare = step(&step(initial));
+        while tortoise != hare {
+            tortoise = step(&tortoise);
+            hare = step(&step(&hare));
+        }
+
+        let mut start = 0;
+        tortoise = initial.clone();
+        while tortoise != hare {
+            tortoise = step(&tortoise);
+            hare = step(&hare);
+            start += 1;
+        }
+
+        let mut length = 1;
+        hare = step(&tortoise);
+        while tortoise != hare {
+            hare = step(&hare);
+            length += 1;
+        }
+        Self { start, length }
+    }
+
+    /// The earliest step whose state is the same as the state at `step`.
+    pub fn equivalent_step(&self, step: usize) -> usize {
+        if step < self.start {
+            step
+        } else {
+            self.start + (step - self.start) % self.length
+        }
+    }
+
+    /// Replays the simulation from `initial` to find the state at any step, however far in the future.
+    pub fn state_at<S: Clone>(&self, initial: &S, step: impl Fn(&S) -> S, at: usize) -> S {
+        (0..self.equivalent_step(at)).fold(initial.clone(), |state, _| step(&state))
+    }
+}
+
+/// A cycle along with every state from the initial one up to the end of the first loop.
+#[derive(Debug, Clone)]
+pub struct CycleHistory<S> {
+    pub cycle: Cycle,
+    states: Vec<S>,
+}
+
+impl<S: Clone + Hash + Eq> CycleHistory<S> {
+    /// Steps from `initial`, remembering every state, until one repeats.
+    /// `step` may return `None` to end the simulation, in which case there is no cycle.
+    pub fn detect(initial: S, mut step: impl FnMut(&S) -> Option<S>) -> Option<Self> {
+        let mut seen = HashMap::from([(initial.clone(), 0)]);
+        let mut states = vec![initial];
+        loop {
+            let next = step(states.last().unwrap())?;
+            if let Some(&start) = seen.get(&next) {
+                let cycle = Cycle {
+                    start,
+                    length: states.len() - start,
+                };
+                return Some(Self { cycle, states });
+            }
+            seen.insert(next.clone(), states.len());
+            states.push(next);
+        }
+    }
+
+    /// The state at any step, however far in the future.
+    pub fn state_at(&self, step: usize) -> &S {
+        &self.states[self.cycle.equivalent_step(step)]
+    }
+
+    /// Every distinct state, in the order they were first reached.
+    pub fn states(&self) -> &[S] {
+        &self.states
+    }
+}
@@ -0,0 +1,165 @@
+/// The six directions between hexagonal cells.
+/// Responsibilities:
+/// - Direction operations matching the square grid directions
+/// - Axial coordinate deltas, shared by pointy-top and flat-top layouts
+/// - Parsing compass step strings such as `ne,se,sw` for either layout
+use super::error::{DirectionError, Result};
+use super::{DirectionBehaviour, RotationBehaviour};
+use crate::helpers::point::Point;
+use num_traits::{CheckedAdd, CheckedSub, One, Signed};
+use std::fmt;
+use std::slice::Iter;
+
+/// A step to one of the six neighbouring hexes, clockwise from NorthEast.
+/// Variants are named for pointy-top hexes; `HexLayout` gives the flat-top names for the same steps.
+/// As a Point delta, x is the axial r coordinate (the row) and y the axial q coordinate.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub enum HexDirections {
+    NorthEast,
+    East,
+    SouthEast,
+    SouthWest,
+    West,
+    NorthWest,
+}
+
+impl HexDirections {
+    pub const ALL: [Self; 6] = [
+        HexDirections::NorthEast,
+        HexDirections::East,
+        HexDirections::SouthEast,
+        HexDirections::SouthWest,
+        HexDirections::West,
+        HexDirections::NorthWest,
+    ];
+}
+
+impl DirectionBehaviour for HexDirections {
+    const COUNT: usize = 6;
+    fn from_index(index: usize) -> Option<Self> {
+        Self::ALL.get(index % Self::COUNT).copied()
+    }
+    fn as_index(&self) -> usize {
+        Self::ALL.iter().position(|&d| d == *self).unwrap()
+    }
+    fn iter_all() -> Iter<'static, Self> {
+        Self::ALL.iter()
+    }
+    fn opposite(&self) -> Self {
+        Self::from_index(self.as_index() + 3).unwrap()
+    }
+
+    fn next(&self) -> Self {
+        Self::from_index(self.as_index() + 1).unwrap()
+    }
+
+    fn previous(&self) -> Self {
+        Self::from_index(self.as_index() + Self::COUNT - 1).unwrap()
+    }
+
+    fn as_delta<P>(&self) -> Point<P>
+    where
+        P: Signed,
+    {
+        match self {
+            Self::NorthEast => Point::new(-P::one(), P::one()),
+            Self::East => Point::new(P::zero(), P::one()),
+            Self::SouthEast => Point::new(P::one(), P::zero()),
+            Self::SouthWest => Point::new(P::one(), -P::one()),
+            Self::West => Point::new(P::zero(), -P::one()),
+            Self::NorthWest => Point::new(-P::one(), P::zero()),
+        }
+    }
+    fn next_point<P>(&self, point: &Point<P>) -> Option<Point<P>>
+    where
+        P: CheckedAdd + CheckedSub + Copy + One,
+    {
+        match self {
+            Self::NorthEast => point
+                .checked_sub_x(P::one())
+                .and_then(|p| p.checked_add_y(P::one())),
+            Self::East => point.checked_add_y(P::one()),
+            Self::SouthEast => point.checked_add_x(P::one()),
+            Self::SouthWest => point
+                .checked_add_x(P::one())
+                .and_then(|p| p.checked_sub_y(P::one())),
+            Self::West => point.checked_sub_y(P::one()),
+            Self::NorthWest => point.checked_sub_x(P::one()),
+        }
+    }
+}
+impl RotationBehaviour for HexDirections {
+    fn rotate(&self, degrees: i32) -> Self {
+        let index = self.as_index() as i32;
+        let new_index = (index + degrees / 60).rem_euclid(Self::COUNT as i32) as usize;
+        Self::from_index(new_index).unwrap()
+    }
+}
+
+impl fmt::Display for HexDirections {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let c = match self {
+            HexDirections::NorthEast => '↗',
+            HexDirections::East => '→',
+            HexDirections::SouthEast => '↘',
+            HexDirections::SouthWest => '↙',
+            HexDirections::West => '←',
+            HexDirections::NorthWest => '↖',
+        };
+        write!(f, "{}", c)
+    }
+}
+
+/// How hexes are drawn, which decides the compass names of the six directions.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub enum HexLayout {
+    /// A vertex at the top: neighbours are e, se, sw, w, nw and ne.
+    PointyTop,
+    /// An edge at the top: neighbours are n, ne, se, s, sw and nw.
+    FlatTop,
+}
+
+impl HexLayout {
+    /// Lowercase compass names for each of `HexDirections::ALL`.
+    fn names(self) -> [&'static str; 6] {
+        match self {
+            HexLayout::PointyTop => ["ne", "e", "se", "sw", "w", "nw"],
+            HexLayout::FlatTop => ["ne", "se", "s", "sw", "nw", "n"],
+        }
+    }
+
+    /// The compass name of `direction` in this layout.
+    pub fn name(self, direction: HexDirections) -> &'static str {
+        self.names()[direction.as_index()]
+    }
+
+    /// Parses a single compass name, ignoring case.
+    pub fn parse_direction(self, name: &str) -> Result<HexDirections> {
+        let name = name.trim().to_ascii_lowercase();
+        self.names()
+            .iter()
+            .position(|&candidate| candidate == name)
+            .map(|idx| HexDirections::ALL[idx])
+            .ok_or_else(|| DirectionError::parse(format!("'{name}' is not a {self:?} direction")))
+    }
+
+    /// Parses a run of compass steps, separated by commas or whitespace or run together as in `esenee`.
+    pub fn parse_steps(self, input: &str) -> Result<Vec<HexDirections>> {
+        let mut steps = Vec::new();
+        let mut chars = input
+            .chars()
+            .filter(|c| *c != ',' && !c.is_whitespace())
+            .map(|c| c.to_ascii_lowercase())
+            .peekable();
+        while let Some(first) = chars.next() {
+            let mut name = first.to_string();
+            if matches!(first, 'n' | 's') {
+                if let Some(second) = chars.next_if(|&c| c == 'e' || c == 'w') {
+                    name.push(second);
+                }
+            }
+            steps.push(self.parse_direction(&name)?);
+        }
+        Ok(steps)
+    }
+}
@@ -4,11 +4,20 @@
 /// - Directional movement
 /// - Directional iteration
 /// - Conversion to coordinate deltas
+/// - Hexagonal directions and their compass names
+/// - Parsing from puzzle notations and turn instruction streams
 use super::point::Point;
-use num_traits::{CheckedAdd, CheckedSub, One, Signed, Zero};
+use num_traits::{CheckedAdd, CheckedSub, NumCast, One, Signed, ToPrimitive, Zero};
 use std::fmt::{self, Debug, Display};
 use std::slice::Iter;
 
+mod error;
+mod hex;
+mod parse;
+pub use error::DirectionError;
+pub use hex::{HexDirections, HexLayout};
+pub use parse::{TurnInstruction, TurnInstructions};
+
 pub trait DirectionBehaviour: Copy + Eq + Sized {
     const COUNT: usize;
 
@@ -476,3 +485,19 @@ where
         Some(self)
     }
 }
+impl<P, D> DirectionalMove<P, D>
+where
+    D: DirectionBehaviour,
+    P: ToPrimitive + NumCast + Copy,
+{
+    /// The point reached after `steps`, wrapping around the edges of `bounds` (exclusive). Runs in O(1) for any number of steps.
+    pub fn get_next_point_wrapping(&self, bounds: &Point<P>) -> Option<Point<P>> {
+        let delta = self.direction.as_delta::<i128>().scale(self.steps as i128);
+        let point = Point::new(self.point.x.to_i128()?, self.point.y.to_i128()?);
+        (point + delta).wrapped_as(bounds)
+    }
+    pub fn next_point_wrapping(mut self, bounds: &Point<P>) -> Option<Self> {
+        self.point = self.get_next_point_wrapping(bounds)?;
+        Some(self)
+    }
+}
@@ -0,0 +1,233 @@
+/// Reading directions from puzzle input.
+/// Responsibilities:
+/// - Arrow (`^>v<`), compass (`NESW`) and screen (`UDLR`) notations, as characters or words
+/// - Strict arrow glyphs for locating a walker on a grid
+/// - Relative turn instruction streams such as `R5,L3,F10` or `10R5L5`, walked into DirectionalMoves
+use super::error::{DirectionError, Result};
+use super::{CardinalDirections, DirectionalMove, OctalDirections, RotationBehaviour};
+use crate::helpers::point::Point;
+use num_traits::{NumCast, Signed};
+use std::str::FromStr;
+
+impl CardinalDirections {
+    /// Reads one of the arrows `^>v<` only, so a grid glyph is never mistaken for a letter such as `S`.
+    pub fn from_arrow(c: char) -> Option<Self> {
+        match c {
+            '^' => Some(Self::North),
+            '>' => Some(Self::East),
+            'v' => Some(Self::South),
+            '<' => Some(Self::West),
+            _ => None,
+        }
+    }
+}
+
+impl TryFrom<char> for CardinalDirections {
+    type Error = DirectionError;
+
+    /// Accepts `^>v<`, the arrows used by Display, and `NESW` or `UDLR` in either case.
+    fn try_from(c: char) -> Result<Self> {
+        match c.to_ascii_uppercase() {
+            '^' | '↑' | 'N' | 'U' => Ok(Self::North),
+            '>' | '→' | 'E' | 'R' => Ok(Self::East),
+            'V' | '↓' | 'S' | 'D' => Ok(Self::South),
+            '<' | '←' | 'W' | 'L' => Ok(Self::West),
+            _ => Err(DirectionError::parse(format!(
+                "'{c}' is not a cardinal direction"
+            ))),
+        }
+    }
+}
+
+impl FromStr for CardinalDirections {
+    type Err = DirectionError;
+
+    /// Accepts any single character `try_from` does, or a word such as `north`, `up` or `Right`.
+    fn from_str(s: &str) -> Result<Self> {
+        let s = s.trim();
+        let mut chars = s.chars();
+        if let (Some(c), None) = (chars.next(), chars.next()) {
+            return Self::try_from(c);
+        }
+        match s.to_ascii_lowercase().as_str() {
+            "north" | "up" => Ok(Self::North),
+            "east" | "right" => Ok(Self::East),
+            "south" | "down" => Ok(Self::South),
+            "west" | "left" => Ok(Self::West),
+            _ => Err(DirectionError::parse(format!(
+                "'{s}' is not a cardinal direction"
+            ))),
+        }
+    }
+}
+
+impl TryFrom<char> for OctalDirections {
+    type Error = DirectionError;
+
+    /// Accepts every cardinal character along with the diagonal arrows `↗↘↙↖`.
+    fn try_from(c: char) -> Result<Self> {
+        match c {
+            '↗' => Ok(Self::NorthEast),
+            '↘' => Ok(Self::SouthEast),
+            '↙' => Ok(Self::SouthWest),
+            '↖' => Ok(Self::NorthWest),
+            _ => CardinalDirections::try_from(c)
+                .map(Self::from)
+                .map_err(|_| DirectionError::parse(format!("'{c}' is not an octal direction"))),
+        }
+    }
+}
+
+impl FromStr for OctalDirections {
+    type Err = DirectionError;
+
+    /// Accepts anything `CardinalDirections` does, plus `NE`, `SE`, `SW` and `NW` style compass
+    /// pairs, `UR` style screen pairs and words like `north-east` or `northeast`.
+    fn from_str(s: &str) -> Result<Self> {
+        let s = s.trim();
+        if let Ok(direction) = s.parse::<CardinalDirections>() {
+            return Ok(direction.into());
+        }
+        let mut chars = s.chars();
+        if let (Some(c), None) = (chars.next(), chars.next()) {
+            return Self::try_from(c);
+        }
+        let word: String = s
+            .to_ascii_lowercase()
+            .chars()
+            .filter(|c| !matches!(c, '-' | '_' | ' '))
+            .collect();
+        match word.as_str() {
+            "ne" | "ur" | "northeast" | "upright" => Ok(Self::NorthEast),
+            "se" | "dr" | "southeast" | "downright" => Ok(Self::SouthEast),
+            "sw" | "dl" | "southwest" | "downleft" => Ok(Self::SouthWest),
+            "nw" | "ul" | "northwest" | "upleft" => Ok(Self::NorthWest),
+            _ => Err(DirectionError::parse(format!(
+                "'{s}' is not an octal direction"
+            ))),
+        }
+    }
+}
+
+impl From<CardinalDirections> for OctalDirections {
+    fn from(direction: CardinalDirections) -> Self {
+        match direction {
+            CardinalDirections::North => Self::North,
+            CardinalDirections::East => Self::East,
+            CardinalDirections::South => Self::South,
+            CardinalDirections::West => Self::West,
+        }
+    }
+}
+
+/// One step of a relative turn instruction stream.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub enum TurnInstruction {
+    /// Turn 90° anticlockwise on the spot.
+    Left,
+    /// Turn 90° clockwise on the spot.
+    Right,
+    /// Move this many steps in the current facing.
+    Forward(usize),
+}
+
+impl TurnInstruction {
+    /// Parses instructions like `R5,L3,F10` or `10R5L5`. `L` and `R` turn and any number moves forward,
+    /// so `R5` turns then moves. An `F` only marks a forward move and must be followed by a number.
+    /// Commas and whitespace are ignored.
+    pub fn parse_all(input: &str) -> Result<Vec<TurnInstruction>> {
+        let mut instructions = Vec::new();
+        let mut chars = input
+            .chars()
+            .filter(|c| *c != ',' && !c.is_whitespace())
+            .peekable();
+        while let Some(c) = chars.next() {
+            match c.to_ascii_uppercase() {
+                'L' => instructions.push(TurnInstruction::Left),
+                'R' => instructions.push(TurnInstruction::Right),
+                'F' if chars.peek().is_some_and(char::is_ascii_digit) => {}
+                digit if digit.is_ascii_digit() => {
+                    let mut steps = digit.to_digit(10).unwrap() as usize;
+                    while let Some(next) = chars.next_if(char::is_ascii_digit) {
+                        steps = steps
+                            .checked_mul(10)
+                            .and_then(|s| s.checked_add(next.to_digit(10).unwrap() as usize))
+                            .ok_or_else(|| DirectionError::parse("Step count overflows"))?;
+                    }
+                    instructions.push(TurnInstruction::Forward(steps));
+                }
+                _ => {
+                    return Err(DirectionError::parse(format!(
+                        "'{c}' is not a turn instruction"
+                    )))
+                }
+            }
+        }
+        Ok(instructions)
+    }
+}
+
+/// Walks a turn instruction stream from a start point, yielding a DirectionalMove for every forward
+/// move, starting from where the walker stood and facing the way it moved.
+#[derive(Debug, Clone)]
+pub struct TurnInstructions<P, D> {
+    instructions: std::vec::IntoIter<TurnInstruction>,
+    position: Point<P>,
+    facing: D,
+}
+
+impl<P, D> TurnInstructions<P, D>
+where
+    P: Signed + NumCast + Copy,
+    D: RotationBehaviour,
+{
+    /// Fails if the input is not a turn instruction stream, or a step count does not fit in `P`.
+    pub fn new(input: &str, start: Point<P>, facing: D) -> Result<Self> {
+        let instructions = TurnInstruction::parse_all(input)?;
+        for instruction in &instructions {
+            if let TurnInstruction::Forward(steps) = instruction {
+                P::from(*steps).ok_or_else(|| {
+                    DirectionError::parse(format!("Step count {steps} does not fit the point type"))
+                })?;
+            }
+        }
+        Ok(Self {
+            instructions: instructions.into_iter(),
+            position: start,
+            facing,
+        })
+    }
+
+    /// Where the walker stands after every move yielded so far.
+    pub fn position(&self) -> Point<P> {
+        self.position
+    }
+
+    /// The way the walker faces after every instruction consumed so far.
+    pub fn facing(&self) -> D {
+        self.facing
+    }
+}
+
+impl<P, D> Iterator for TurnInstructions<P, D>
+where
+    P: Signed + NumCast + Copy,
+    D: RotationBehaviour,
+{
+    type Item = DirectionalMove<P, D>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            match self.instructions.next()? {
+                TurnInstruction::Left => self.facing = self.facing.rotate_270(),
+                TurnInstruction::Right => self.facing = self.facing.rotate_90(),
+                TurnInstruction::Forward(steps) => {
+                    let step = DirectionalMove::new(self.position, self.facing).with_steps(steps);
+                    let distance = P::from(steps).expect("step counts are checked when parsing");
+                    self.position = self.position + self.facing.as_delta::<P>().scale(distance);
+                    return Some(step);
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,33 @@
+use std::fmt;
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+pub enum GraphError {
+    #[error("Parse error: {0}")]
+    ParseError(String),
+
+    #[error("Graph contains a cycle through nodes {0:?}")]
+    Cycle(Vec<usize>),
+
+    #[error("Unknown node: {0}")]
+    UnknownNode(String),
+
+    #[error("Operation error: {0}")]
+    OperationError(String),
+}
+
+pub type Result<T> = std::result::Result<T, GraphError>;
+
+impl GraphError {
+    pub fn parse<M: fmt::Display>(message: M) -> Self {
+        Self::ParseError(message.to_string())
+    }
+
+    pub fn unknown_node<M: fmt::Debug>(node: M) -> Self {
+        Self::UnknownNode(format!("{:?}", node))
+    }
+
+    pub fn operation<M: fmt::Display>(message: M) -> Self {
+        Self::OperationError(message.to_string())
+    }
+}
@@ -0,0 +1,360 @@
+/// A directed graph over interned node keys.
+/// Responsibilities:
+/// - Interning keys (strings or any hashable ID) to dense node indices
+/// - Adjacency-list storage with weighted edges
+/// - Parsing the `a: b c d` adjacency format
+/// - Topological sort with cycle reporting
+/// - Path counting, optionally through must-visit nodes
+/// - Strongly connected components
+/// - Shortest paths
+use std::cmp::Reverse;
+use std::collections::{BinaryHeap, HashMap};
+use std::hash::Hash;
+
+mod error;
+pub use error::GraphError;
+use error::Result;
+
+/// Maps keys to dense indices, in the order they were first seen.
+#[derive(Debug, Clone)]
+pub struct Interner<K> {
+    ids: HashMap<K, usize>,
+    keys: Vec<K>,
+}
+
+impl<K> Default for Interner<K> {
+    fn default() -> Self {
+        Self {
+            ids: HashMap::new(),
+            keys: Vec::new(),
+        }
+    }
+}
+
+impl<K: Hash + Eq + Clone> Interner<K> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The index of `key`, assigning the next free index if it has not been seen before.
+    pub fn intern(&mut self, key: K) -> usize {
+        if let Some(&id) = self.ids.get(&key) {
+            return id;
+        }
+        let id = self.keys.len();
+        self.ids.insert(key.clone(), id);
+        self.keys.push(key);
+        id
+    }
+
+    pub fn id(&self, key: &K) -> Option<usize> {
+        self.ids.get(key).copied()
+    }
+
+    pub fn key(&self, id: usize) -> Option<&K> {
+        self.keys.get(id)
+    }
+
+    pub fn len(&self) -> usize {
+        self.keys.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.keys.is_empty()
+    }
+}
+
+/// A directed graph. Nodes are addressed by their interned index, edges carry a weight (1 unless given).
+#[derive(Debug, Clone)]
+pub struct Graph<K> {
+    nodes: Interner<K>,
+    edges: Vec<Vec<(usize, u64)>>,
+}
+
+impl<K> Default for Graph<K> {
+    fn default() -> Self {
+        Self {
+            nodes: Interner::default(),
+            edges: Vec::new(),
+        }
+    }
+}
+
+impl<K: Hash + Eq + Clone> Graph<K> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The index of the node for `key`, adding it if needed.
+    pub fn add_node(&mut self, key: K) -> usize {
+        let id = self.nodes.intern(key);
+        if id == self.edges.len() {
+            self.edges.push(Vec::new());
+        }
+        id
+    }
+
+    pub fn add_edge(&mut self, from: K, to: K) -> (usize, usize) {
+        self.add_weighted_edge(from, to, 1)
+    }
+
+    pub fn add_weighted_edge(&mut self, from: K, to: K, weight: u64) -> (usize, usize) {
+        let from = self.add_node(from);
+        let to = self.add_node(to);
+        self.edges[from].push((to, weight));
+        (from, to)
+    }
+
+    pub fn id(&self, key: &K) -> Option<usize> {
+        self.nodes.id(key)
+    }
+
+    /// The index of the node for `key`, or an error if there is no such node.
+    pub fn require(&self, key: &K) -> Result<usize>
+    where
+        K: std::fmt::Debug,
+    {
+        self.id(key).ok_or_else(|| GraphError::unknown_node(key))
+    }
+
+    pub fn key(&self, id: usize) -> Option<&K> {
+        self.nodes.key(id)
+    }
+
+    pub fn node_count(&self) -> usize {
+        self.edges.len()
+    }
+
+    pub fn edge_count(&self) -> usize {
+        self.edges.iter().map(Vec::len).sum()
+    }
+
+    /// Outgoing (node, weight) edges of `id`.
+    pub fn edges(&self, id: usize) -> &[(usize, u64)] {
+        &self.edges[id]
+    }
+
+    pub fn neighbours(&self, id: usize) -> impl Iterator<Item = usize> + '_ {
+        self.edges[id].iter().map(|&(to, _)| to)
+    }
+
+    /// Orders the nodes so every edge points forwards, using Kahn's algorithm.
+    /// Fails with the nodes of one cycle, in order, if there is no such ordering.
+    pub fn topological_sort(&self) -> Result<Vec<usize>> {
+        let mut in_degree = vec![0; self.node_count()];
+        self.edges
+            .iter()
+            .flatten()
+            .for_each(|&(to, _)| in_degree[to] += 1);
+
+        let mut ready: Vec<usize> = (0..self.node_count())
+            .filter(|&id| in_degree[id] == 0)
+            .collect();
+        let mut order = Vec::with_capacity(self.node_count());
+        while let Some(id) = ready.pop() {
+            order.push(id);
+            for to in self.neighbours(id) {
+                in_degree[to] -= 1;
+                if in_degree[to] == 0 {
+                    ready.push(to);
+                }
+            }
+        }
+
+        if order.len() == self.node_count() {
+            return Ok(order);
+        }
+        // Every node left over has a predecessor that is also left over, so walking backwards must loop.
+        let mut predecessor = vec![None; self.node_count()];
+        for (from, edges) in self.edges.iter().enumerate() {
+            for &(to, _) in edges.iter().filter(|_| in_degree[from] > 0) {
+                predecessor[to] = Some(from);
+            }
+        }
+        let mut seen = vec![false; self.node_count()];
+        let mut current = (0..self.node_count())
+            .find(|&id| in_degree[id] > 0)
+            .unwrap();
+        while !seen[current] {
+            seen[current] = true;
+            current = predecessor[current].unwrap();
+        }
+        let mut cycle = vec![current];
+        let mut next = predecessor[current].unwrap();
+        while next != current {
+            cycle.push(next);
+            next = predecessor[next].unwrap();
+        }
+        cycle.reverse();
+        Err(GraphError::Cycle(cycle))
+    }
+
+    /// Number of distinct paths from `from` to `to`.
+    /// Fails if the graph has a cycle, as there could be infinitely many paths.
+    pub fn count_paths(&self, from: usize, to: usize) -> Result<u64> {
+        self.count_paths_via(from, to, &[])
+    }
+
+    /// Number of distinct paths from `from` to `to` that pass through every node in `must_visit`.
+    /// Fails if the graph has a cycle, if the count overflows, or with 64 or more must-visit nodes.
+    pub fn count_paths_via(&self, from: usize, to: usize, must_visit: &[usize]) -> Result<u64> {
+        let mut must_visit = must_visit.to_vec();
+        must_visit.sort_unstable();
+        must_visit.dedup();
+        if must_visit.len() >= u64::BITS as usize {
+            return Err(GraphError::operation(format!(
+                "Too many must-visit nodes, got {}",
+                must_visit.len()
+            )));
+        }
+        let masks = 1 << must_visit.len();
+
+        // Paths from each node to `to`, bucketed by the set of must-visit nodes they pass through as a bitmask.
+        // Walking the topological order backwards settles every successor before the nodes leading to it.
+        let mut counts = vec![Vec::new(); self.node_count()];
+        for node in self.topological_sort()?.into_iter().rev() {
+            let bit = must_visit
+                .iter()
+                .position(|&id| id == node)
+                .map_or(0, |idx| 1 << idx);
+            let mut node_counts = vec![0u64; masks];
+            if node == to {
+                node_counts[bit] = 1;
+            } else {
+                for next in self.neighbours(node) {
+                    for (mask, &count) in counts[next].iter().enumerate() {
+                        node_counts[mask | bit] = node_counts[mask | bit]
+                            .checked_add(count)
+                            .ok_or_else(|| GraphError::operation("Path count overflows a u64"))?;
+                    }
+                }
+            }
+            counts[node] = node_counts;
+        }
+        Ok(counts[from][masks - 1])
+    }
+
+    /// Strongly connected components using Tarjan's algorithm.
+    /// Components come out in reverse topological order, so every edge between components points to an earlier one.
+    pub fn strongly_connected_components(&self) -> Vec<Vec<usize>> {
+        let count = self.node_count();
+        let mut index = vec![None; count];
+        let mut low = vec![0; count];
+        let mut on_stack = vec![false; count];
+        let mut stack = Vec::new();
+        let mut components = Vec::new();
+        let mut next_index = 0;
+
+        for root in 0..count {
+            if index[root].is_some() {
+                continue;
+            }
+            // (node, position of the next edge to explore)
+            let mut calls = vec![(root, 0)];
+            index[root] = Some(next_index);
+            low[root] = next_index;
+            next_index += 1;
+            stack.push(root);
+            on_stack[root] = true;
+
+            while let Some(&(node, edge)) = calls.last() {
+                if let Some(&(next, _)) = self.edges[node].get(edge) {
+                    calls.last_mut().unwrap().1 += 1;
+                    match index[next] {
+                        None => {
+                            index[next] = Some(next_index);
+                            low[next] = next_index;
+                            next_index += 1;
+                            stack.push(next);
+                            on_stack[next] = true;
+                            calls.push((next, 0));
+                        }
+                        Some(next_idx) if on_stack[next] => {
+                            low[node] = low[node].min(next_idx);
+                        }
+                        Some(_) => {}
+                    }
+                    continue;
+                }
+
+                calls.pop();
+                if let Some(&(parent, _)) = calls.last() {
+                    low[parent] = low[parent].min(low[node]);
+                }
+                if Some(low[node]) == index[node] {
+                    let mut component = Vec::new();
+                    while let Some(member) = stack.pop() {
+                        on_stack[member] = false;
+                        component.push(member);
+                        if member == node {
+                            break;
+                        }
+                    }
+                    components.push(component);
+                }
+            }
+        }
+        components
+    }
+
+    fn dijkstra(&self, from: usize, to: Option<usize>) -> (Vec<Option<u64>>, Vec<Option<usize>>) {
+        let mut distances = vec![None; self.node_count()];
+        let mut previous = vec![None; self.node_count()];
+        distances[from] = Some(0);
+
+        let mut heap = BinaryHeap::from([Reverse((0, from))]);
+        while let Some(Reverse((distance, node))) = heap.pop() {
+            if distances[node].is_some_and(|best| best < distance) {
+                continue;
+            }
+            if to == Some(node) {
+                break;
+            }
+            for &(next, weight) in &self.edges[node] {
+                let candidate = distance + weight;
+                if distances[next].is_some_and(|best| best <= candidate) {
+                    continue;
+                }
+                distances[next] = Some(candidate);
+                previous[next] = Some(node);
+                heap.push(Reverse((candidate, next)));
+            }
+        }
+        (distances, previous)
+    }
+
+    /// Shortest distance from `from` to every node, `None` where unreachable.
+    pub fn shortest_paths(&self, from: usize) -> Vec<Option<u64>> {
+        self.dijkstra(from, None).0
+    }
+
+    /// Shortest distance from `from` to `to` along with the nodes on the way, both ends inclusive.
+    pub fn shortest_path(&self, from: usize, to: usize) -> Option<(u64, Vec<usize>)> {
+        let (distances, previous) = self.dijkstra(from, Some(to));
+        let distance = distances[to]?;
+        let mut path = vec![to];
+        while let Some(node) = previous[*path.last().unwrap()] {
+            path.push(node);
+        }
+        path.reverse();
+        Some((distance, path))
+    }
+}
+
+impl<'a> Graph<&'a str> {
+    /// Parses lines of the form `a: b c d`, adding an edge from `a` to each of `b`, `c` and `d`.
+    pub fn parse_adjacency(input: &'a str) -> Result<Self> {
+        let mut graph = Self::new();
+        for line in input.lines().map(str::trim).filter(|line| !line.is_empty()) {
+            let (from, rest) = line
+                .split_once(':')
+                .ok_or_else(|| GraphError::parse(format!("Missing ':' in line {:?}", line)))?;
+            let from = from.trim();
+            graph.add_node(from);
+            for to in rest.split_whitespace() {
+                graph.add_edge(from, to);
+            }
+        }
+        Ok(graph)
+    }
+}
@@ -0,0 +1,157 @@
+/// A cellular automaton stepping a Grid.
+/// Responsibilities:
+/// - Neighbourhood definitions (cardinal, octal or custom offsets)
+/// - Double-buffered generation stepping, optionally rayon-parallel by row
+/// - Running until the grid stops changing
+/// - Tracking how many cells changed in each generation
+use super::{Grid, GridPoint, WorldPoint};
+use crate::helpers::direction::{CardinalDirections, DirectionBehaviour, OctalDirections};
+use rayon::prelude::*;
+
+/// The cells that count as neighbours of a cell.
+#[derive(Debug, Clone)]
+pub enum Neighbourhood {
+    Cardinal,
+    Octal,
+    /// Arbitrary (row, col) offsets from the cell.
+    Custom(Vec<WorldPoint>),
+}
+
+impl Neighbourhood {
+    pub fn offsets(&self) -> Vec<WorldPoint> {
+        match self {
+            Self::Cardinal => CardinalDirections::ALL.map(|d| d.as_delta()).to_vec(),
+            Self::Octal => OctalDirections::ALL.map(|d| d.as_delta()).to_vec(),
+            Self::Custom(offsets) => offsets.clone(),
+        }
+    }
+}
+
+/// A cell and its surroundings, as seen by an automaton rule.
+pub struct CellView<'a, T> {
+    pub point: GridPoint,
+    pub value: &'a T,
+    grid: &'a Grid<T>,
+    offsets: &'a [WorldPoint],
+}
+
+impl<'a, T> CellView<'a, T> {
+    /// Values of the in-bounds neighbours.
+    pub fn neighbours(&self) -> impl Iterator<Item = &'a T> + '_ {
+        self.offsets.iter().filter_map(|offset| {
+            let x = self.point.x.checked_add_signed(offset.x as isize)?;
+            let y = self.point.y.checked_add_signed(offset.y as isize)?;
+            self.grid.get_ref(GridPoint::new(x, y)).ok()
+        })
+    }
+
+    /// Number of in-bounds neighbours for which `predicate` holds.
+    pub fn count(&self, predicate: impl Fn(&T) -> bool) -> usize {
+        self.neighbours().filter(|v| predicate(v)).count()
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Automaton<T> {
+    current: Grid<T>,
+    next: Grid<T>,
+    offsets: Vec<WorldPoint>,
+    parallel: bool,
+    changes: Vec<usize>,
+}
+
+impl<T> Automaton<T>
+where
+    T: Clone + PartialEq + Send + Sync,
+{
+    pub fn new(grid: Grid<T>, neighbourhood: Neighbourhood) -> Self {
+        Self {
+            next: grid.clone(),
+            current: grid,
+            offsets: neighbourhood.offsets(),
+            parallel: false,
+            changes: Vec::new(),
+        }
+    }
+
+    /// Evaluate each generation's rows in parallel with rayon.
+    pub fn with_parallel(mut self, parallel: bool) -> Self {
+        self.parallel = parallel;
+        self
+    }
+
+    pub fn grid(&self) -> &Grid<T> {
+        &self.current
+    }
+
+    pub fn into_grid(self) -> Grid<T> {
+        self.current
+    }
+
+    /// Number of generations stepped so far.
+    pub fn generation(&self) -> usize {
+        self.changes.len()
+    }
+
+    /// Number of cells that changed in each generation so far.
+    pub fn changes(&self) -> &[usize] {
+        &self.changes
+    }
+
+    /// Advances one generation, replacing every cell with the result of `rule`. Returns the number of cells that changed.
+    pub fn step(&mut self, rule: impl Fn(&CellView<T>) -> T + Sync) -> usize {
+        if self.current.data.is_empty() {
+            // Nothing to update, and rows cannot be chunked when there are no columns.
+            self.changes.push(0);
+            return 0;
+        }
+        let current = &self.current;
+        let offsets = &self.offsets;
+        let cols = current.cols;
+        let update_row = |(row, cells): (usize, &mut [T])| {
+            cells
+                .iter_mut()
+                .enumerate()
+                .map(|(col, cell)| {
+                    let point = GridPoint::new(row, col);
+                    let value = &current[point];
+                    let view = CellView {
+                        point,
+                        value,
+                        grid: current,
+                        offsets,
+                    };
+                    *cell = rule(&view);
+                    (cell != value) as usize
+                })
+                .sum::<usize>()
+        };
+
+        let changed = if self.parallel {
+            self.next
+                .data
+                .par_chunks_mut(cols)
+                .enumerate()
+                .map(update_row)
+                .sum()
+        } else {
+            self.next
+                .data
+                .chunks_mut(cols)
+                .enumerate()
+                .map(update_row)
+                .sum()
+        };
+
+        std::mem::swap(&mut self.current, &mut self.next);
+        self.changes.push(changed);
+        changed
+    }
+
+    /// Steps until a generation changes nothing. Returns the number of generations that changed at least one cell.
+    pub fn run_until_stable(&mut self, rule: impl Fn(&CellView<T>) -> T + Sync) -> usize {
+        let start = self.generation();
+        while self.step(&rule) > 0 {}
+        self.generation() - start - 1
+    }
+}
@@ -0,0 +1,333 @@
+/// A bit-packed 2D grid of booleans.
+/// Responsibilities:
+/// - Compact storage, one bit per cell, each row packed into u64 words
+/// - Word-level set operations (union, intersection, difference)
+/// - Shifting the whole grid by a direction
+/// - Neighbour counting kernels evaluated a word at a time
+/// - Conversion to and from Grid<bool> and CharGrid
+use super::error::{GridError, Result};
+use super::{BoolGrid, CharGrid, Grid, GridPoint};
+use crate::helpers::direction::DirectionBehaviour;
+use std::fmt;
+
+const WORD_BITS: usize = u64::BITS as usize;
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct BitGrid {
+    words: Vec<u64>,
+    words_per_row: usize,
+    pub rows: usize,
+    pub cols: usize,
+}
+
+impl BitGrid {
+    pub fn new(rows: usize, cols: usize) -> Self {
+        let words_per_row = cols.div_ceil(WORD_BITS);
+        Self {
+            words: vec![0; rows * words_per_row],
+            words_per_row,
+            rows,
+            cols,
+        }
+    }
+
+    pub fn size(&self) -> usize {
+        self.rows * self.cols
+    }
+
+    pub fn bounds(&self) -> GridPoint {
+        (self.rows, self.cols).into()
+    }
+
+    fn locate(&self, point: GridPoint) -> Result<(usize, u64)> {
+        if !point.check_bounds(&self.bounds()) {
+            return Err(GridError::out_of_bounds(
+                point.x, point.y, self.rows, self.cols,
+            ));
+        }
+        let word = point.x * self.words_per_row + point.y / WORD_BITS;
+        Ok((word, 1 << (point.y % WORD_BITS)))
+    }
+
+    /// Mask of the valid bits in the last word of each row.
+    fn tail_mask(&self) -> u64 {
+        match self.cols % WORD_BITS {
+            0 => u64::MAX,
+            bits => (1 << bits) - 1,
+        }
+    }
+
+    fn row_words(&self, row: usize) -> &[u64] {
+        &self.words[row * self.words_per_row..(row + 1) * self.words_per_row]
+    }
+
+    pub fn get(&self, point: GridPoint) -> Result<bool> {
+        let (word, mask) = self.locate(point)?;
+        Ok(self.words[word] & mask != 0)
+    }
+
+    pub fn set(&mut self, point: GridPoint, val: bool) -> Option<()> {
+        let (word, mask) = self.locate(point).ok()?;
+        if val {
+            self.words[word] |= mask;
+        } else {
+            self.words[word] &= !mask;
+        }
+        Some(())
+    }
+
+    /// Number of set cells.
+    pub fn count_ones(&self) -> usize {
+        self.words.iter().map(|w| w.count_ones() as usize).sum()
+    }
+
+    pub fn any(&self) -> bool {
+        self.words.iter().any(|&w| w != 0)
+    }
+
+    /// Points of every set cell, in row-major order.
+    pub fn iter_ones(&self) -> impl Iterator<Item = GridPoint> + '_ {
+        self.words.iter().enumerate().flat_map(move |(idx, &word)| {
+            let row = idx / self.words_per_row;
+            let col_offset = (idx % self.words_per_row) * WORD_BITS;
+            std::iter::successors((word != 0).then_some(word), |&w| {
+                let rest = w & (w - 1);
+                (rest != 0).then_some(rest)
+            })
+            .map(move |w| GridPoint::new(row, col_offset + w.trailing_zeros() as usize))
+        })
+    }
+
+    fn check_dimensions(&self, other: &Self) -> Result<()> {
+        if self.rows != other.rows || self.cols != other.cols {
+            return Err(GridError::operation(format!(
+                "Mismatched dimensions {}x{} and {}x{}",
+                self.rows, self.cols, other.rows, other.cols
+            )));
+        }
+        Ok(())
+    }
+
+    fn combine_with(&mut self, other: &Self, op: impl Fn(u64, u64) -> u64) -> Result<()> {
+        self.check_dimensions(other)?;
+        self.words
+            .iter_mut()
+            .zip(&other.words)
+            .for_each(|(a, &b)| *a = op(*a, b));
+        Ok(())
+    }
+
+    pub fn union_with(&mut self, other: &Self) -> Result<()> {
+        self.combine_with(other, |a, b| a | b)
+    }
+
+    pub fn intersect_with(&mut self, other: &Self) -> Result<()> {
+        self.combine_with(other, |a, b| a & b)
+    }
+
+    pub fn difference_with(&mut self, other: &Self) -> Result<()> {
+        self.combine_with(other, |a, b| a & !b)
+    }
+
+    pub fn union(&self, other: &Self) -> Result<Self> {
+        let mut result = self.clone();
+        result.union_with(other)?;
+        Ok(result)
+    }
+
+    pub fn intersection(&self, other: &Self) -> Result<Self> {
+        let mut result = self.clone();
+        result.intersect_with(other)?;
+        Ok(result)
+    }
+
+    pub fn difference(&self, other: &Self) -> Result<Self> {
+        let mut result = self.clone();
+        result.difference_with(other)?;
+        Ok(result)
+    }
+
+    /// Flips every cell.
+    pub fn invert(&mut self) {
+        let tail = self.tail_mask();
+        for (idx, word) in self.words.iter_mut().enumerate() {
+            *word = !*word;
+            if idx % self.words_per_row == self.words_per_row - 1 {
+                *word &= tail;
+            }
+        }
+    }
+
+    /// Moves every cell by (rows, cols). Cells shifted past an edge are dropped and vacated cells are cleared.
+    pub fn shifted(&self, rows: isize, cols: isize) -> Self {
+        let mut result = Self::new(self.rows, self.cols);
+        let tail = self.tail_mask();
+        for row in 0..self.rows {
+            let Some(source) = row
+                .checked_add_signed(-rows)
+                .filter(|&source| source < self.rows)
+            else {
+                continue;
+            };
+            let start = row * self.words_per_row;
+            let destination = &mut result.words[start..start + self.words_per_row];
+            shift_words(self.row_words(source), destination, cols);
+            if let Some(last) = destination.last_mut() {
+                *last &= tail;
+            }
+        }
+        result
+    }
+
+    /// Moves every cell one step in `direction`.
+    pub fn shifted_in<D: DirectionBehaviour>(&self, direction: D) -> Self {
+        let delta = direction.as_delta::<isize>();
+        self.shifted(delta.x, delta.y)
+    }
+
+    /// Bit-sliced count of the set neighbours of every cell over the neighbourhood `D`.
+    /// Plane `i` holds bit `i` of each cell's count.
+    fn neighbour_count_planes<D>(&self) -> [Vec<u64>; 4]
+    where
+        D: DirectionBehaviour + 'static,
+    {
+        let mut planes: [Vec<u64>; 4] = std::array::from_fn(|_| vec![0; self.words.len()]);
+        for &direction in D::iter_all() {
+            // Looking at the neighbour in `direction` is the same as shifting the grid the opposite way.
+            let shifted = self.shifted_in(direction.opposite());
+            for (idx, &word) in shifted.words.iter().enumerate() {
+                let mut carry = word;
+                for plane in planes.iter_mut() {
+                    let sum = plane[idx] ^ carry;
+                    carry &= plane[idx];
+                    plane[idx] = sum;
+                }
+            }
+        }
+        planes
+    }
+
+    /// Number of set neighbours of every cell over the neighbourhood `D`.
+    pub fn neighbour_counts<D>(&self) -> Grid<u8>
+    where
+        D: DirectionBehaviour + 'static,
+    {
+        let planes = self.neighbour_count_planes::<D>();
+        let mut counts = Grid::new(self.rows, self.cols, 0);
+        for (idx, count) in counts.data.iter_mut().enumerate() {
+            let (row, col) = (idx / self.cols, idx % self.cols);
+            let word = row * self.words_per_row + col / WORD_BITS;
+            let bit = col % WORD_BITS;
+            *count = planes
+                .iter()
+                .enumerate()
+                .map(|(i, plane)| (((plane[word] >> bit) & 1) as u8) << i)
+                .sum();
+        }
+        counts
+    }
+
+    /// Cells whose number of set neighbours over the neighbourhood `D` satisfies `rule`.
+    pub fn neighbours_matching<D>(&self, rule: impl Fn(u8) -> bool) -> Self
+    where
+        D: DirectionBehaviour + 'static,
+    {
+        let planes = self.neighbour_count_planes::<D>();
+        let matching: Vec<u8> = (0..=D::COUNT as u8).filter(|&n| rule(n)).collect();
+        let mut result = Self::new(self.rows, self.cols);
+        let tail = self.tail_mask();
+        for (idx, word) in result.words.iter_mut().enumerate() {
+            *word = matching.iter().fold(0, |acc, &n| {
+                acc | planes.iter().enumerate().fold(u64::MAX, |eq, (i, plane)| {
+                    eq & if n >> i & 1 == 1 {
+                        plane[idx]
+                    } else {
+                        !plane[idx]
+                    }
+                })
+            });
+            if idx % self.words_per_row == self.words_per_row - 1 {
+                *word &= tail;
+            }
+        }
+        result
+    }
+
+    pub fn from_char_grid(grid: &CharGrid, on: char) -> Self {
+        Self::from_fn(grid, |&c| c == on)
+    }
+
+    pub fn to_char_grid(&self, on: char, off: char) -> CharGrid {
+        let mut grid = Grid::new(self.rows, self.cols, off);
+        for point in self.iter_ones() {
+            grid[point] = on;
+        }
+        grid
+    }
+
+    fn from_fn<T>(grid: &Grid<T>, is_set: impl Fn(&T) -> bool) -> Self {
+        let mut bits = Self::new(grid.rows, grid.cols);
+        for (idx, val) in grid.data.iter().enumerate() {
+            if is_set(val) {
+                bits.set(grid.idx_to_point(idx), true);
+            }
+        }
+        bits
+    }
+}
+
+/// Shifts a row of bits towards higher columns when `by` is positive, lower when negative.
+fn shift_words(source: &[u64], destination: &mut [u64], by: isize) {
+    let len = source.len();
+    let word_shift = by.unsigned_abs() / WORD_BITS;
+    let bit_shift = by.unsigned_abs() % WORD_BITS;
+    for (w, word) in destination.iter_mut().enumerate() {
+        let (main, spill) = if by >= 0 {
+            (
+                w.checked_sub(word_shift).map(|i| source[i] << bit_shift),
+                w.checked_sub(word_shift + 1)
+                    .filter(|_| bit_shift > 0)
+                    .map(|i| source[i] >> (WORD_BITS - bit_shift)),
+            )
+        } else {
+            (
+                Some(w + word_shift)
+                    .filter(|&i| i < len)
+                    .map(|i| source[i] >> bit_shift),
+                Some(w + word_shift + 1)
+                    .filter(|&i| i < len && bit_shift > 0)
+                    .map(|i| source[i] << (WORD_BITS - bit_shift)),
+            )
+        };
+        *word = main.unwrap_or(0) | spill.unwrap_or(0);
+    }
+}
+
+impl From<&BoolGrid> for BitGrid {
+    fn from(grid: &BoolGrid) -> Self {
+        Self::from_fn(grid, |&b| b)
+    }
+}
+
+impl From<&BitGrid> for BoolGrid {
+    fn from(bits: &BitGrid) -> Self {
+        let mut grid = Grid::new(bits.rows, bits.cols, false);
+        for point in bits.iter_ones() {
+            grid[point] = true;
+        }
+        grid
+    }
+}
+
+impl fmt::Display for BitGrid {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for row in 0..self.rows {
+            for col in 0..self.cols {
+                let set = self.get((row, col).into()).unwrap();
+                write!(f, "{}", if set { '#' } else { '.' })?;
+            }
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
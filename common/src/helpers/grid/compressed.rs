@@ -0,0 +1,287 @@
+/// A dense Grid standing in for a huge, sparsely used coordinate space.
+/// Responsibilities:
+/// - Compressing each axis so every interesting coordinate gets its own cell and every gap between them one more
+/// - Mapping between real points and compressed cells
+/// - Tracking the real width and height of every cell
+/// - Turning cell counts back into true areas, including fast rectangle queries
+use super::error::{GridError, Result};
+use super::{Grid, GridPoint};
+use crate::helpers::point::Point;
+use num_traits::PrimInt;
+use std::ops::Range;
+
+/// One compressed axis. Each interesting coordinate is a cell of width 1, and each gap between two
+/// of them becomes a single cell as wide as the gap.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CompressedAxis<P> {
+    /// First real coordinate of every cell, followed by one past the end of the last cell.
+    starts: Vec<P>,
+}
+
+impl<P: PrimInt> CompressedAxis<P> {
+    /// A value of `P::max_value()` gets no cell, as the end of its cell cannot be represented.
+    pub fn new(values: impl IntoIterator<Item = P>) -> Self {
+        let mut values: Vec<P> = values.into_iter().collect();
+        values.sort_unstable();
+        values.dedup();
+
+        let mut starts = Vec::with_capacity(values.len() * 2);
+        for pair in values.windows(2) {
+            starts.push(pair[0]);
+            // A gap too wide to represent is certainly wider than one.
+            if pair[1]
+                .checked_sub(&pair[0])
+                .is_none_or(|gap| gap > P::one())
+            {
+                starts.push(pair[0] + P::one());
+            }
+        }
+        if let Some(&last) = values.last() {
+            starts.push(last);
+            if let Some(end) = last.checked_add(&P::one()) {
+                starts.push(end);
+            }
+        }
+        Self { starts }
+    }
+
+    /// Number of cells.
+    pub fn len(&self) -> usize {
+        self.starts.len().saturating_sub(1)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// The cell covering `value`, if it is within the axis.
+    pub fn cell_of(&self, value: P) -> Option<usize> {
+        let idx = self.starts.partition_point(|&start| start <= value);
+        (idx > 0 && idx < self.starts.len()).then(|| idx - 1)
+    }
+
+    /// The real coordinates covered by `cell`.
+    pub fn range(&self, cell: usize) -> Range<P> {
+        self.starts[cell]..self.starts[cell + 1]
+    }
+
+    /// How many real coordinates `cell` covers.
+    pub fn width(&self, cell: usize) -> P {
+        self.starts[cell + 1] - self.starts[cell]
+    }
+}
+
+/// A Grid over compressed axes, with each cell standing for a block of real points.
+#[derive(Debug, Clone)]
+pub struct CompressedGrid<T, P> {
+    pub grid: Grid<T>,
+    row_axis: CompressedAxis<P>,
+    column_axis: CompressedAxis<P>,
+}
+
+impl<T: Clone, P: PrimInt> CompressedGrid<T, P> {
+    /// Compresses around `points`, filling every cell with `default`.
+    /// A non-zero `padding` adds a border of that real width on every side, so flood fills can reach around the outside.
+    pub fn new(points: impl IntoIterator<Item = Point<P>>, padding: P, default: T) -> Result<Self> {
+        let points: Vec<Point<P>> = points.into_iter().collect();
+        if points.is_empty() {
+            return Err(GridError::builder("No points"));
+        }
+        let axis = |coord: fn(&Point<P>) -> P| -> Result<CompressedAxis<P>> {
+            let min = points
+                .iter()
+                .map(coord)
+                .min()
+                .expect("points are not empty");
+            let max = points
+                .iter()
+                .map(coord)
+                .max()
+                .expect("points are not empty");
+            let border = if padding > P::zero() {
+                let low = min.checked_sub(&padding);
+                let high = max.checked_add(&padding);
+                let (Some(low), Some(high)) = (low, high) else {
+                    return Err(GridError::builder(
+                        "Padding reaches past the range of the coordinate type",
+                    ));
+                };
+                vec![low, high]
+            } else {
+                vec![]
+            };
+            Ok(CompressedAxis::new(points.iter().map(coord).chain(border)))
+        };
+        let row_axis = axis(|p| p.x)?;
+        let column_axis = axis(|p| p.y)?;
+        Ok(Self {
+            grid: Grid::build_default(row_axis.len(), column_axis.len(), default)?,
+            row_axis,
+            column_axis,
+        })
+    }
+}
+
+impl<T, P: PrimInt> CompressedGrid<T, P> {
+    pub fn row_axis(&self) -> &CompressedAxis<P> {
+        &self.row_axis
+    }
+
+    pub fn column_axis(&self) -> &CompressedAxis<P> {
+        &self.column_axis
+    }
+
+    /// The cell covering a real point.
+    pub fn cell_of(&self, point: Point<P>) -> Option<GridPoint> {
+        Some(Point::new(
+            self.row_axis.cell_of(point.x)?,
+            self.column_axis.cell_of(point.y)?,
+        ))
+    }
+
+    /// Inclusive (min, max) real corners of a cell.
+    pub fn cell_bounds(&self, cell: GridPoint) -> (Point<P>, Point<P>) {
+        let rows = self.row_axis.range(cell.x);
+        let columns = self.column_axis.range(cell.y);
+        (
+            Point::new(rows.start, columns.start),
+            Point::new(rows.end - P::one(), columns.end - P::one()),
+        )
+    }
+
+    /// Number of real points a cell stands for.
+    pub fn cell_area(&self, cell: GridPoint) -> P {
+        self.row_axis.width(cell.x) * self.column_axis.width(cell.y)
+    }
+
+    /// The value of the cell covering a real point.
+    pub fn get_ref(&self, point: Point<P>) -> Option<&T> {
+        self.grid.get_ref(self.cell_of(point)?).ok()
+    }
+
+    /// Sets every cell overlapping the inclusive rectangle between real points `a` and `b`, such as a line between two of them.
+    pub fn fill(&mut self, a: Point<P>, b: Point<P>, value: T) -> Result<()>
+    where
+        T: Clone,
+    {
+        let (min, max) = self.cell_span(a, b)?;
+        for row in min.x..=max.x {
+            for column in min.y..=max.y {
+                self.grid[Point::new(row, column)] = value.clone();
+            }
+        }
+        Ok(())
+    }
+
+    fn cell_span(&self, a: Point<P>, b: Point<P>) -> Result<(GridPoint, GridPoint)> {
+        let cell = |point: Point<P>| {
+            self.cell_of(point).ok_or_else(|| {
+                GridError::operation("Point lies outside the compressed coordinates")
+            })
+        };
+        let (a, b) = (cell(a)?, cell(b)?);
+        Ok((
+            Point::new(a.x.min(b.x), a.y.min(b.y)),
+            Point::new(a.x.max(b.x), a.y.max(b.y)),
+        ))
+    }
+
+    /// True area of a set of cells, such as the points of a flood filled Region.
+    pub fn area(&self, cells: impl IntoIterator<Item = GridPoint>) -> P {
+        cells
+            .into_iter()
+            .fold(P::zero(), |acc, cell| acc + self.cell_area(cell))
+    }
+
+    /// True area of every cell whose value matches `predicate`.
+    pub fn area_where(&self, predicate: impl Fn(&T) -> bool) -> P {
+        self.area(
+            self.grid
+                .iter()
+                .enumerate()
+                .filter(|(_, val)| predicate(val))
+                .map(|(idx, _)| self.grid.idx_to_point(idx)),
+        )
+    }
+
+    /// A summed-area table over the cells matching `predicate`, answering rectangle area queries in constant time.
+    pub fn summed_area(&self, predicate: impl Fn(&T) -> bool) -> SummedArea<'_, T, P> {
+        let mut sums = Grid::new(self.grid.rows + 1, self.grid.cols + 1, P::zero());
+        for row in 0..self.grid.rows {
+            for column in 0..self.grid.cols {
+                let cell = Point::new(row, column);
+                let own = if predicate(&self.grid[cell]) {
+                    self.cell_area(cell)
+                } else {
+                    P::zero()
+                };
+                sums[Point::new(row + 1, column + 1)] =
+                    own + sums[Point::new(row, column + 1)] + sums[Point::new(row + 1, column)]
+                        - sums[Point::new(row, column)];
+            }
+        }
+        SummedArea { owner: self, sums }
+    }
+}
+
+/// Prefix sums of true cell areas. See [`CompressedGrid::summed_area`].
+#[derive(Debug, Clone)]
+pub struct SummedArea<'a, T, P> {
+    owner: &'a CompressedGrid<T, P>,
+    sums: Grid<P>,
+}
+
+impl<T, P: PrimInt> SummedArea<'_, T, P> {
+    /// True area of matching cells overlapping the inclusive rectangle between real points `a` and `b`.
+    /// Exact when both corners are among the compressed points, since cells then line up with the rectangle.
+    pub fn area(&self, a: Point<P>, b: Point<P>) -> Result<P> {
+        let (min, max) = self.owner.cell_span(a, b)?;
+        let sum = |row: usize, column: usize| self.sums[Point::new(row, column)];
+        // Add the overlap back first so unsigned sums never dip below zero.
+        Ok(sum(max.x + 1, max.y + 1) + sum(min.x, min.y)
+            - sum(min.x, max.y + 1)
+            - sum(max.x + 1, min.y))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn unsigned_coordinates() {
+        let points = [
+            Point::new(0u64, 0),
+            Point::new(0, 100),
+            Point::new(50, 100),
+            Point::new(50, 0),
+        ];
+        let mut grid = CompressedGrid::new(points, 0, false).unwrap();
+        grid.fill(Point::new(40, 0), Point::new(50, 100), true)
+            .unwrap();
+
+        // Only rows 40 to 50 are filled, so row 0 holds nothing filled while the whole grid holds all of it.
+        let filled = grid.summed_area(|&cell| cell);
+        assert_eq!(
+            filled.area(Point::new(0, 0), Point::new(0, 100)).unwrap(),
+            0
+        );
+        assert_eq!(
+            filled.area(Point::new(0, 0), Point::new(50, 100)).unwrap(),
+            grid.area_where(|&cell| cell)
+        );
+
+        assert!(CompressedGrid::new(points, 1, false).is_err());
+    }
+
+    #[test]
+    fn axis_at_the_limits_of_the_type() {
+        let axis = CompressedAxis::new([0u8, u8::MAX]);
+        assert_eq!(axis.len(), 2);
+        assert_eq!(axis.cell_of(200), Some(1));
+        assert_eq!(axis.cell_of(u8::MAX), None);
+
+        let axis = CompressedAxis::new([i8::MIN, i8::MAX]);
+        assert_eq!(axis.range(1), -127..127);
+    }
+}
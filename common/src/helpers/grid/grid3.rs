@@ -0,0 +1,233 @@
+/// A dense 3D grid of voxels.
+/// Responsibilities:
+/// - Value storage and access by Point3
+/// - Neighbourhood operations over face, edge or vertex connectivity
+/// - Flood fill, including the exterior air around a shape
+/// - Counting exposed surface area
+use super::error::{GridError, Result};
+use crate::helpers::point::{Connectivity3, Point3};
+use std::collections::VecDeque;
+use std::ops::{Index, IndexMut};
+
+pub type GridPoint3 = Point3<usize>;
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Grid3<T> {
+    data: Vec<T>,
+    dimensions: GridPoint3,
+}
+
+impl<T> Grid3<T> {
+    pub fn new(dimensions: GridPoint3, value: T) -> Self
+    where
+        T: Clone,
+    {
+        Self {
+            data: vec![value; dimensions.x * dimensions.y * dimensions.z],
+            dimensions,
+        }
+    }
+
+    /// Sizes along x, y and z. Every valid point is below these.
+    pub fn dimensions(&self) -> GridPoint3 {
+        self.dimensions
+    }
+
+    pub fn size(&self) -> usize {
+        self.data.len()
+    }
+
+    fn index_of(&self, point: GridPoint3) -> Result<usize> {
+        if !point.check_bounds(&self.dimensions) {
+            return Err(GridError::operation(format!(
+                "Point {} out of bounds {}",
+                point, self.dimensions
+            )));
+        }
+        Ok((point.x * self.dimensions.y + point.y) * self.dimensions.z + point.z)
+    }
+
+    fn point_of(&self, idx: usize) -> GridPoint3 {
+        let z = idx % self.dimensions.z;
+        let y = idx / self.dimensions.z % self.dimensions.y;
+        let x = idx / (self.dimensions.y * self.dimensions.z);
+        Point3::new(x, y, z)
+    }
+
+    pub fn get_ref(&self, point: GridPoint3) -> Result<&T> {
+        Ok(&self.data[self.index_of(point)?])
+    }
+
+    pub fn get_mut(&mut self, point: GridPoint3) -> Result<&mut T> {
+        let idx = self.index_of(point)?;
+        Ok(&mut self.data[idx])
+    }
+
+    pub fn set(&mut self, point: GridPoint3, val: T) -> Option<()> {
+        let idx = self.index_of(point).ok()?;
+        self.data[idx] = val;
+        Some(())
+    }
+
+    /// Every point with its value, in x, then y, then z order.
+    pub fn iter(&self) -> impl Iterator<Item = (GridPoint3, &T)> + '_ {
+        self.data
+            .iter()
+            .enumerate()
+            .map(|(idx, val)| (self.point_of(idx), val))
+    }
+
+    /// In-bounds neighbours of a point.
+    pub fn neighbours(
+        &self,
+        point: GridPoint3,
+        connectivity: Connectivity3,
+    ) -> impl Iterator<Item = (GridPoint3, &T)> + '_ {
+        point
+            .neighbours(connectivity)
+            .filter_map(|next| Some((next, self.get_ref(next).ok()?)))
+    }
+
+    /// Every point reachable from any of `starts` through cells where `passable` holds.
+    pub fn flood_fill(
+        &self,
+        starts: impl IntoIterator<Item = GridPoint3>,
+        connectivity: Connectivity3,
+        passable: impl Fn(&T) -> bool,
+    ) -> Grid3<bool> {
+        let mut seen = Grid3::new(self.dimensions, false);
+        let mut queue: VecDeque<GridPoint3> = starts
+            .into_iter()
+            .filter(|&start| self.get_ref(start).is_ok_and(&passable))
+            .collect();
+        queue.iter().for_each(|&start| {
+            seen.set(start, true);
+        });
+
+        while let Some(point) = queue.pop_front() {
+            for (next, val) in self.neighbours(point, connectivity) {
+                if !seen[next] && passable(val) {
+                    seen[next] = true;
+                    queue.push_back(next);
+                }
+            }
+        }
+        seen
+    }
+
+    /// Points on the outside faces of the grid.
+    fn boundary_points(&self) -> impl Iterator<Item = GridPoint3> + '_ {
+        let Point3 { x, y, z } = self.dimensions;
+        (0..self.size())
+            .map(|idx| self.point_of(idx))
+            .filter(move |p| {
+                p.x == 0 || p.y == 0 || p.z == 0 || p.x == x - 1 || p.y == y - 1 || p.z == z - 1
+            })
+    }
+
+    /// Cells not in the shape that can be reached from outside the grid, moving through faces.
+    pub fn exterior(&self, is_solid: impl Fn(&T) -> bool) -> Grid3<bool> {
+        self.flood_fill(self.boundary_points(), Connectivity3::Face, |v| {
+            !is_solid(v)
+        })
+    }
+
+    /// Number of faces of solid cells that touch a non-solid cell or the edge of the grid.
+    pub fn surface_area(&self, is_solid: impl Fn(&T) -> bool) -> usize {
+        self.exposed_faces(&is_solid, |val| !is_solid(val))
+    }
+
+    /// Number of faces of solid cells that touch the exterior air or the edge of the grid.
+    /// Unlike `surface_area` this ignores air pockets sealed inside the shape.
+    pub fn exterior_surface_area(&self, is_solid: impl Fn(&T) -> bool) -> usize {
+        let exterior = self.exterior(&is_solid);
+        self.data
+            .iter()
+            .enumerate()
+            .filter(|(_, val)| is_solid(val))
+            .map(|(idx, _)| {
+                let point = self.point_of(idx);
+                let inside = exterior
+                    .neighbours(point, Connectivity3::Face)
+                    .filter(|(_, &outside)| !outside)
+                    .count();
+                Connectivity3::Face.count() - inside
+            })
+            .sum()
+    }
+
+    fn exposed_faces(&self, is_solid: impl Fn(&T) -> bool, open: impl Fn(&T) -> bool) -> usize {
+        self.data
+            .iter()
+            .enumerate()
+            .filter(|(_, val)| is_solid(val))
+            .map(|(idx, _)| {
+                let neighbours: Vec<_> = self
+                    .neighbours(self.point_of(idx), Connectivity3::Face)
+                    .collect();
+                let off_grid = Connectivity3::Face.count() - neighbours.len();
+                off_grid + neighbours.iter().filter(|(_, val)| open(val)).count()
+            })
+            .sum()
+    }
+}
+
+impl Grid3<bool> {
+    /// A grid just large enough for `points` plus `padding` empty cells on every side,
+    /// along with the offset to add to a point to find its cell.
+    pub fn from_points(
+        points: impl IntoIterator<Item = Point3<i32>>,
+        padding: usize,
+    ) -> Result<(Self, Point3<i32>)> {
+        let points: Vec<Point3<i32>> = points.into_iter().collect();
+        let Some(&first) = points.first() else {
+            return Err(GridError::builder("No points"));
+        };
+        let (min, max) = points.iter().fold((first, first), |(min, max), p| {
+            (
+                Point3::new(min.x.min(p.x), min.y.min(p.y), min.z.min(p.z)),
+                Point3::new(max.x.max(p.x), max.y.max(p.y), max.z.max(p.z)),
+            )
+        });
+        let overflow = || GridError::builder("Points are too far apart to fit in a grid");
+        let padding_offset = i32::try_from(padding)?;
+        let offset = Point3::new(padding_offset, padding_offset, padding_offset)
+            .checked_sub(min)
+            .ok_or_else(overflow)?;
+        let extent = |low: i32, high: i32| {
+            (high.abs_diff(low) as usize)
+                .checked_add(1)?
+                .checked_add(padding.checked_mul(2)?)
+        };
+        let dimensions = Point3::new(
+            extent(min.x, max.x).ok_or_else(overflow)?,
+            extent(min.y, max.y).ok_or_else(overflow)?,
+            extent(min.z, max.z).ok_or_else(overflow)?,
+        );
+
+        let mut grid = Self::new(dimensions, false);
+        for point in points {
+            let cell = point
+                .checked_add(offset)
+                .ok_or_else(overflow)?
+                .try_cast::<usize>()
+                .map_err(GridError::conversion)?;
+            grid.set(cell, true);
+        }
+        Ok((grid, offset))
+    }
+}
+
+impl<T> Index<GridPoint3> for Grid3<T> {
+    type Output = T;
+
+    fn index(&self, point: GridPoint3) -> &Self::Output {
+        self.get_ref(point).expect("point should be in bounds")
+    }
+}
+
+impl<T> IndexMut<GridPoint3> for Grid3<T> {
+    fn index_mut(&mut self, point: GridPoint3) -> &mut Self::Output {
+        self.get_mut(point).expect("point should be in bounds")
+    }
+}
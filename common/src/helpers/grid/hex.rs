@@ -0,0 +1,110 @@
+/// A hexagon shaped grid of hexagonal cells.
+/// Responsibilities:
+/// - Value storage and access by Hex for every cell within a radius of the origin
+/// - Boundary enforcement
+/// - Neighbourhood operations over the six HexDirections
+use super::error::{GridError, Result};
+use crate::helpers::direction::HexDirections;
+use crate::helpers::point::Hex;
+use std::ops::{Index, IndexMut};
+
+pub type HexPoint = Hex<i32>;
+
+/// Every hex within `radius` steps of the origin, stored densely.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct HexGrid<T> {
+    /// A (2 * radius + 1) square over (r, q), with the corners outside the hexagon left unused.
+    data: Vec<T>,
+    radius: i32,
+}
+
+impl<T> HexGrid<T> {
+    pub fn new(radius: usize, value: T) -> Result<Self>
+    where
+        T: Clone,
+    {
+        let too_large = || GridError::builder(format!("Radius {radius} is too large"));
+        let radius = i32::try_from(radius)?;
+        let side = radius
+            .checked_mul(2)
+            .and_then(|diameter| diameter.checked_add(1))
+            .ok_or_else(too_large)? as usize;
+        let cells = side.checked_mul(side).ok_or_else(too_large)?;
+        Ok(Self {
+            data: vec![value; cells],
+            radius,
+        })
+    }
+
+    pub fn radius(&self) -> usize {
+        self.radius as usize
+    }
+
+    /// Number of cells in the hexagon.
+    pub fn size(&self) -> usize {
+        let radius = self.radius();
+        3 * radius * (radius + 1) + 1
+    }
+
+    pub fn contains(&self, hex: HexPoint) -> bool {
+        hex.length() <= self.radius
+    }
+
+    fn index_of(&self, hex: HexPoint) -> Result<usize> {
+        if !self.contains(hex) {
+            return Err(GridError::operation(format!(
+                "Hex {} is more than {} steps from the origin",
+                hex, self.radius
+            )));
+        }
+        // Both offsets are within 0..side once the hex is known to be inside.
+        let side = 2 * self.radius() + 1;
+        let offset = |coord: i32| (coord + self.radius) as usize;
+        Ok(offset(hex.r) * side + offset(hex.q))
+    }
+
+    pub fn get_ref(&self, hex: HexPoint) -> Result<&T> {
+        Ok(&self.data[self.index_of(hex)?])
+    }
+
+    pub fn get_mut(&mut self, hex: HexPoint) -> Result<&mut T> {
+        let idx = self.index_of(hex)?;
+        Ok(&mut self.data[idx])
+    }
+
+    pub fn set(&mut self, hex: HexPoint, val: T) -> Option<()> {
+        let idx = self.index_of(hex).ok()?;
+        self.data[idx] = val;
+        Some(())
+    }
+
+    /// Every cell with its value, spiralling out from the origin.
+    pub fn iter(&self) -> impl Iterator<Item = (HexPoint, &T)> + '_ {
+        HexPoint::origin()
+            .spiral(self.radius)
+            .map(|hex| (hex, &self[hex]))
+    }
+
+    /// In-bounds neighbours of a cell, clockwise from NorthEast.
+    pub fn neighbours(
+        &self,
+        hex: HexPoint,
+    ) -> impl Iterator<Item = (HexDirections, HexPoint, &T)> + '_ {
+        hex.neighbours()
+            .filter_map(|(direction, next)| Some((direction, next, self.get_ref(next).ok()?)))
+    }
+}
+
+impl<T> Index<HexPoint> for HexGrid<T> {
+    type Output = T;
+
+    fn index(&self, hex: HexPoint) -> &Self::Output {
+        self.get_ref(hex).expect("hex should be in bounds")
+    }
+}
+
+impl<T> IndexMut<HexPoint> for HexGrid<T> {
+    fn index_mut(&mut self, hex: HexPoint) -> &mut Self::Output {
+        self.get_mut(hex).expect("hex should be in bounds")
+    }
+}
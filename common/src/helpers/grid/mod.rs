@@ -3,8 +3,8 @@
 /// - Value storage and access
 /// - Boundry enforcement
 /// - Neighbourhood operations
-/// - Search operations
-/// - Traversal operations
+/// - Search operations, including word and stencil patterns
+/// - Traversal operations, including ray casting
 use super::direction::DirectionBehaviour;
 use super::direction::DirectionalMove;
 use super::point::Point;
@@ -14,8 +14,34 @@ use std::convert::From;
 use std::fmt::{self, Display};
 use std::ops::{Index, IndexMut, Range};
 
+mod automaton;
+mod bits;
+mod compressed;
 mod error;
+mod grid3;
+mod hex;
+mod pattern;
+mod ray;
+mod rectangle;
+mod region;
+mod search;
+mod sparse;
+mod transform;
+mod wrapping;
+pub use automaton::{Automaton, CellView, Neighbourhood};
+pub use bits::BitGrid;
+pub use compressed::{CompressedAxis, CompressedGrid, SummedArea};
 use error::{GridError, Result};
+pub use grid3::{Grid3, GridPoint3};
+pub use hex::{HexGrid, HexPoint};
+pub use pattern::{Overlap, Stencil, WordMatch};
+pub use ray::Ray;
+pub use rectangle::Rectangle;
+pub use region::{Region, Regions};
+pub use search::SearchResult;
+pub use sparse::{SparseGrid, SparseSearchResult};
+pub use transform::Transform;
+pub use wrapping::WrappingGrid;
 /// Type aliases for common grid patterns
 pub type GridPoint = Point<usize>;
 pub type WorldPoint = Point<i32>;
@@ -23,7 +49,7 @@ pub type WorldPoint = Point<i32>;
 pub type BoolGrid = Grid<bool>;
 pub type CharGrid = Grid<char>;
 pub type IntGrid = Grid<i32>;
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub struct Grid<T> {
     data: Vec<T>,
     pub rows: usize,
@@ -102,70 +128,35 @@ impl<T> Grid<T> {
     }
 }
 
+/// A read-only window onto part of a Grid, always within the grid's bounds.
 pub struct GridView<'a, T> {
     grid: &'a Grid<T>,
     bounds: Rectangle,
 }
 
 impl<T> GridView<'_, T> {
-    pub fn iter(&self) -> impl Iterator<Item = (GridPoint, &T)> {
+    pub fn bounds(&self) -> Rectangle {
         self.bounds
-            .points()
-            .filter_map(|p| self.grid.get_ref(p).ok().map(|v| (p, v)))
     }
-}
-impl<T> Index<GridPoint> for GridView<'_, T> {
-    type Output = T;
 
-    fn index(&self, point: GridPoint) -> &Self::Output {
-        self.grid.get_ref(point).unwrap()
+    /// The value at `point`, if it is inside the view.
+    pub fn get_ref(&self, point: GridPoint) -> Option<&T> {
+        self.bounds
+            .contains(point)
+            .then(|| &self.grid.data[point.x * self.grid.cols + point.y])
     }
-}
-
-#[derive(Debug, Clone, Copy)]
-pub struct Rectangle {
-    pub top_left: GridPoint,
-    pub bottom_right: GridPoint,
-}
 
-impl Rectangle {
-    pub fn new(top_left: impl Into<GridPoint>, bottom_right: impl Into<GridPoint>) -> Self {
-        Self {
-            top_left: top_left.into(),
-            bottom_right: bottom_right.into(),
-        }
+    pub fn iter(&self) -> impl Iterator<Item = (GridPoint, &T)> {
+        self.bounds.points().map(|p| (p, &self.grid[p]))
     }
+}
 
-    pub fn entire_grid(bound: impl Into<GridPoint>) -> Self {
-        Self {
-            top_left: GridPoint::zero(),
-            bottom_right: bound.into(),
-        }
-    }
+impl<T> Index<GridPoint> for GridView<'_, T> {
+    type Output = T;
 
-    pub fn points(&self) -> impl Iterator<Item = GridPoint> + use<'_> {
-        (self.top_left.x..self.bottom_right.x)
-            .flat_map(move |x| (self.top_left.y..self.bottom_right.y).map(move |y| (x, y).into()))
-    }
-    pub fn around_point(center: impl Into<GridPoint>, radius: impl Into<Option<usize>>) -> Self {
-        let center = center.into();
-        let radius = radius.into().unwrap_or(1);
-        Self {
-            top_left: (
-                center.x.saturating_sub(radius),
-                center.y.saturating_sub(radius),
-            )
-                .into(),
-            bottom_right: (center.x + radius, center.y + radius).into(),
-        }
-    }
-}
-impl From<(GridPoint, GridPoint)> for Rectangle {
-    fn from((top_left, bottom_right): (GridPoint, GridPoint)) -> Self {
-        Self {
-            top_left,
-            bottom_right,
-        }
+    fn index(&self, point: GridPoint) -> &Self::Output {
+        self.get_ref(point)
+            .expect("point should be inside the view")
     }
 }
 
@@ -183,6 +174,18 @@ impl<T> Grid<T> {
     pub fn size(&self) -> usize {
         self.rows * self.cols
     }
+    /// The (rows, cols) bound of the grid, exclusive.
+    pub fn bounds(&self) -> GridPoint {
+        (self.rows, self.cols).into()
+    }
+
+    /// A view of the cells of `rectangle`, clamped to the grid.
+    pub fn view(&self, rectangle: Rectangle) -> GridView<'_, T> {
+        GridView {
+            grid: self,
+            bounds: rectangle.clamp(self.bounds()),
+        }
+    }
 }
 
 // Generic access methods
@@ -273,6 +276,19 @@ impl<T> Grid<T> {
         self.step_in_direction(mover)
             .and_then(|p| self.get_ref(p).ok())
     }
+
+    /// In-bounds neighbours of a point for the given neighbourhood, e.g. `CardinalDirections`.
+    pub fn neighbours<D>(&self, point: GridPoint) -> impl Iterator<Item = (GridPoint, &T)> + '_
+    where
+        D: DirectionBehaviour + 'static,
+    {
+        let bounds = self.bounds();
+        D::iter_all().filter_map(move |direction| {
+            direction
+                .next_point_in_bounds(&point, &bounds)
+                .map(|p| (p, &self[p]))
+        })
+    }
 }
 
 impl<T, P> Index<Point<P>> for Grid<T>
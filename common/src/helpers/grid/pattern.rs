@@ -0,0 +1,186 @@
+/// Pattern matching over a Grid.
+/// Responsibilities:
+/// - Finding words or value sequences read along any DirectionBehaviour
+/// - Choosing whether matches may share cells
+/// - Matching 2D stencils with wildcard cells, including their rotated and mirrored variants
+use super::error::Result;
+use super::{Grid, GridPoint, Transform};
+use crate::helpers::direction::{DirectionBehaviour, DirectionalMove};
+
+/// Whether matches may share cells.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub enum Overlap {
+    /// Report every match, even ones using the same cells.
+    Allow,
+    /// Skip any match sharing a cell with one already reported, taking starts in reading order and
+    /// directions in the order of `D::iter_all`.
+    Disjoint,
+}
+
+/// A sequence found in a grid, read from `start` in `direction`.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub struct WordMatch<D> {
+    pub start: GridPoint,
+    pub direction: D,
+    pub len: usize,
+}
+
+impl<D: DirectionBehaviour> WordMatch<D> {
+    /// The cells of the match, from `start` onwards.
+    pub fn points(&self) -> impl Iterator<Item = GridPoint> + '_ {
+        (0..self.len).map(|step| {
+            DirectionalMove::new(self.start, self.direction)
+                .with_steps(step)
+                .get_next_point()
+                .expect("matched cells are on the grid")
+        })
+    }
+}
+
+impl<T: PartialEq> Grid<T> {
+    /// Every place `needle` can be read along one of the directions of `D`.
+    pub fn find_sequence<D>(&self, needle: &[T], overlap: Overlap) -> Vec<WordMatch<D>>
+    where
+        D: DirectionBehaviour + 'static,
+    {
+        let Some(first) = needle.first() else {
+            return Vec::new();
+        };
+        let mut used = Grid::new(self.rows, self.cols, false);
+        let mut matches = Vec::new();
+        for (idx, _) in self
+            .data
+            .iter()
+            .enumerate()
+            .filter(|(_, val)| *val == first)
+        {
+            let start = self.idx_to_point(idx);
+            for &direction in D::iter_all() {
+                let found = WordMatch {
+                    start,
+                    direction,
+                    len: needle.len(),
+                };
+                if !self.reads_at(start, direction, needle) {
+                    continue;
+                }
+                if overlap == Overlap::Disjoint {
+                    if found.points().any(|point| used[point]) {
+                        continue;
+                    }
+                    found.points().for_each(|point| used[point] = true);
+                }
+                matches.push(found);
+            }
+        }
+        matches
+    }
+
+    fn reads_at<D: DirectionBehaviour>(
+        &self,
+        start: GridPoint,
+        direction: D,
+        needle: &[T],
+    ) -> bool {
+        let bounds = self.bounds();
+        let mut point = Some(start);
+        needle.iter().all(|expected| {
+            let Some(current) = point else {
+                return false;
+            };
+            point = direction.next_point_in_bounds(&current, &bounds);
+            self[current] == *expected
+        })
+    }
+
+    /// Every top left corner where `stencil` matches under one of `transforms`, with the transform used.
+    /// Transforms giving the same stencil as an earlier one are skipped, so symmetric stencils match once per place.
+    pub fn find_stencil(
+        &self,
+        stencil: &Stencil<T>,
+        transforms: impl IntoIterator<Item = Transform>,
+    ) -> Vec<(GridPoint, Transform)>
+    where
+        T: Clone,
+    {
+        let variants = stencil.variants(transforms);
+        let mut matches = Vec::new();
+        for row in 0..self.rows {
+            for col in 0..self.cols {
+                let corner = GridPoint::new(row, col);
+                matches.extend(
+                    variants
+                        .iter()
+                        .filter(|(variant, _)| variant.matches_at(self, corner))
+                        .map(|&(_, transform)| (corner, transform)),
+                );
+            }
+        }
+        matches
+    }
+}
+
+impl Grid<char> {
+    /// Every place `word` can be read along one of the directions of `D`.
+    pub fn find_word<D>(&self, word: &str, overlap: Overlap) -> Vec<WordMatch<D>>
+    where
+        D: DirectionBehaviour + 'static,
+    {
+        let needle: Vec<char> = word.chars().collect();
+        self.find_sequence(&needle, overlap)
+    }
+}
+
+/// A small rectangular pattern where `None` cells match anything.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Stencil<T> {
+    cells: Grid<Option<T>>,
+}
+
+impl<T> Stencil<T> {
+    pub fn new(cells: Grid<Option<T>>) -> Self {
+        Self { cells }
+    }
+
+    pub fn bounds(&self) -> GridPoint {
+        self.cells.bounds()
+    }
+
+    /// Whether the stencil fits on `grid` with its top left corner at `corner` and every fixed cell matches.
+    pub fn matches_at(&self, grid: &Grid<T>, corner: GridPoint) -> bool
+    where
+        T: PartialEq,
+    {
+        corner.x + self.cells.rows <= grid.rows
+            && corner.y + self.cells.cols <= grid.cols
+            && self.cells.iter().enumerate().all(|(idx, cell)| {
+                cell.as_ref()
+                    .is_none_or(|expected| grid[corner + self.cells.idx_to_point(idx)] == *expected)
+            })
+    }
+
+    /// The distinct stencils produced by `transforms`, each with the first transform giving it.
+    pub fn variants(
+        &self,
+        transforms: impl IntoIterator<Item = Transform>,
+    ) -> Vec<(Self, Transform)>
+    where
+        T: Clone + PartialEq,
+    {
+        let mut variants: Vec<(Self, Transform)> = Vec::new();
+        for transform in transforms {
+            let variant = Self::new(self.cells.transformed(transform));
+            if variants.iter().all(|(seen, _)| *seen != variant) {
+                variants.push((variant, transform));
+            }
+        }
+        variants
+    }
+}
+
+impl Stencil<char> {
+    /// Reads a stencil from rows of text, where `wildcard` matches any character.
+    pub fn parse(pattern: &str, wildcard: char) -> Result<Self> {
+        Grid::build_mapped(pattern, |c| (c != wildcard).then_some(c)).map(Self::new)
+    }
+}
@@ -0,0 +1,91 @@
+/// Straight line walks across a Grid.
+/// Responsibilities:
+/// - Casting rays from a point along a direction or any integer step vector
+/// - Stopping before or at a blocking cell
+/// - Finding the first visible cell in every direction of a neighbourhood
+use super::{Grid, GridPoint};
+use crate::helpers::direction::DirectionBehaviour;
+use crate::helpers::point::Point;
+use num_traits::{Signed, ToPrimitive};
+
+/// The cells hit by repeatedly adding a step to a start point, not including the start, until the edge of the grid.
+#[derive(Debug, Clone)]
+pub struct Ray<'a, T> {
+    grid: &'a Grid<T>,
+    current: Point<i64>,
+    step: Point<i64>,
+}
+
+impl<'a, T> Ray<'a, T> {
+    /// Stops before the first cell where `blocked` holds.
+    pub fn stop_before(
+        self,
+        blocked: impl Fn(&T) -> bool,
+    ) -> impl Iterator<Item = (GridPoint, &'a T)> {
+        self.take_while(move |(_, val)| !blocked(val))
+    }
+
+    /// Stops after yielding the first cell where `blocked` holds.
+    pub fn stop_at(self, blocked: impl Fn(&T) -> bool) -> impl Iterator<Item = (GridPoint, &'a T)> {
+        let mut done = false;
+        self.take_while(move |(_, val)| {
+            let keep = !done;
+            done = blocked(val);
+            keep
+        })
+    }
+}
+
+impl<'a, T> Iterator for Ray<'a, T> {
+    type Item = (GridPoint, &'a T);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.step.is_origin() {
+            return None;
+        }
+        self.current += self.step;
+        let point = self.current.in_bounds_as(&self.grid.bounds()).ok()?;
+        Some((point, &self.grid[point]))
+    }
+}
+
+impl<T> Grid<T> {
+    /// A ray from `start` one cell at a time in `direction`.
+    pub fn cast_ray<D: DirectionBehaviour>(&self, start: GridPoint, direction: D) -> Ray<'_, T> {
+        self.cast_ray_by(start, direction.as_delta::<i64>())
+    }
+
+    /// A ray from `start` moving by `step` each time, skipping the cells in between.
+    /// A zero step, or one too large to represent, gives an empty ray.
+    pub fn cast_ray_by<P>(&self, start: GridPoint, step: Point<P>) -> Ray<'_, T>
+    where
+        P: Signed + ToPrimitive + Copy,
+    {
+        let step = match (step.x.to_i64(), step.y.to_i64()) {
+            (Some(x), Some(y)) => Point::new(x, y),
+            _ => Point::zero(),
+        };
+        Ray {
+            grid: self,
+            current: start.map(|coord| coord as i64),
+            step,
+        }
+    }
+
+    /// The first cell in each direction of `D` from `start` for which `visible` holds, skipping over the rest.
+    /// Directions that reach the edge without finding one are left out.
+    pub fn first_visible<'a, D>(
+        &'a self,
+        start: GridPoint,
+        visible: impl Fn(&T) -> bool + 'a,
+    ) -> impl Iterator<Item = (D, GridPoint, &'a T)> + 'a
+    where
+        D: DirectionBehaviour + 'static,
+    {
+        D::iter_all().filter_map(move |&direction| {
+            self.cast_ray(start, direction)
+                .find(|(_, val)| visible(val))
+                .map(|(point, val)| (direction, point, val))
+        })
+    }
+}
@@ -0,0 +1,191 @@
+/// Axis aligned boxes of grid cells.
+/// Responsibilities:
+/// - Construction from corners, a whole grid or a neighbourhood around a point
+/// - Measurements (width, height, area, perimeter)
+/// - Containment of points and other rectangles
+/// - Intersection, bounding union and subtraction into disjoint pieces
+/// - Clamping to grid bounds
+use super::GridPoint;
+use std::ops::Range;
+
+/// A box of cells from `top_left` inclusive to `bottom_right` exclusive.
+/// It is empty when `bottom_right` is not below and to the right of `top_left`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct Rectangle {
+    pub top_left: GridPoint,
+    pub bottom_right: GridPoint,
+}
+
+impl Rectangle {
+    pub fn new(top_left: impl Into<GridPoint>, bottom_right: impl Into<GridPoint>) -> Self {
+        Self {
+            top_left: top_left.into(),
+            bottom_right: bottom_right.into(),
+        }
+    }
+
+    /// The smallest rectangle containing both cells, given in any order.
+    pub fn from_corners(a: impl Into<GridPoint>, b: impl Into<GridPoint>) -> Self {
+        let (a, b) = (a.into(), b.into());
+        Self::new(
+            (a.x.min(b.x), a.y.min(b.y)),
+            (a.x.max(b.x) + 1, a.y.max(b.y) + 1),
+        )
+    }
+
+    pub fn entire_grid(bound: impl Into<GridPoint>) -> Self {
+        Self {
+            top_left: GridPoint::zero(),
+            bottom_right: bound.into(),
+        }
+    }
+
+    /// Every cell within `radius` of `center` on both axes, defaulting to 1. Not clamped to any grid.
+    pub fn around_point(center: impl Into<GridPoint>, radius: impl Into<Option<usize>>) -> Self {
+        let center = center.into();
+        let radius = radius.into().unwrap_or(1);
+        Self {
+            top_left: (
+                center.x.saturating_sub(radius),
+                center.y.saturating_sub(radius),
+            )
+                .into(),
+            bottom_right: (center.x + radius + 1, center.y + radius + 1).into(),
+        }
+    }
+
+    pub fn rows(&self) -> Range<usize> {
+        self.top_left.x..self.bottom_right.x
+    }
+
+    pub fn columns(&self) -> Range<usize> {
+        self.top_left.y..self.bottom_right.y
+    }
+
+    pub fn height(&self) -> usize {
+        self.rows().len()
+    }
+
+    pub fn width(&self) -> usize {
+        self.columns().len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.height() == 0 || self.width() == 0
+    }
+
+    /// Number of cells.
+    pub fn area(&self) -> usize {
+        self.height() * self.width()
+    }
+
+    /// Number of cell edges around the outside.
+    pub fn perimeter(&self) -> usize {
+        if self.is_empty() {
+            0
+        } else {
+            2 * (self.height() + self.width())
+        }
+    }
+
+    pub fn points(&self) -> impl Iterator<Item = GridPoint> + use<'_> {
+        (self.top_left.x..self.bottom_right.x)
+            .flat_map(move |x| (self.top_left.y..self.bottom_right.y).map(move |y| (x, y).into()))
+    }
+
+    pub fn contains(&self, point: GridPoint) -> bool {
+        self.rows().contains(&point.x) && self.columns().contains(&point.y)
+    }
+
+    /// Whether every cell of `other` is inside. An empty rectangle is inside anything.
+    pub fn contains_rect(&self, other: &Rectangle) -> bool {
+        other.is_empty()
+            || (self.top_left.x <= other.top_left.x
+                && self.top_left.y <= other.top_left.y
+                && other.bottom_right.x <= self.bottom_right.x
+                && other.bottom_right.y <= self.bottom_right.y)
+    }
+
+    /// The cells in both, if there are any.
+    pub fn intersection(&self, other: &Rectangle) -> Option<Rectangle> {
+        let overlap = Self::new(
+            (
+                self.top_left.x.max(other.top_left.x),
+                self.top_left.y.max(other.top_left.y),
+            ),
+            (
+                self.bottom_right.x.min(other.bottom_right.x),
+                self.bottom_right.y.min(other.bottom_right.y),
+            ),
+        );
+        (!overlap.is_empty()).then_some(overlap)
+    }
+
+    pub fn intersects(&self, other: &Rectangle) -> bool {
+        self.intersection(other).is_some()
+    }
+
+    /// The smallest rectangle containing both. Empty rectangles are ignored.
+    pub fn union(&self, other: &Rectangle) -> Rectangle {
+        match (self.is_empty(), other.is_empty()) {
+            (_, true) => *self,
+            (true, false) => *other,
+            (false, false) => Self::new(
+                (
+                    self.top_left.x.min(other.top_left.x),
+                    self.top_left.y.min(other.top_left.y),
+                ),
+                (
+                    self.bottom_right.x.max(other.bottom_right.x),
+                    self.bottom_right.y.max(other.bottom_right.y),
+                ),
+            ),
+        }
+    }
+
+    /// The cells not in `other`, as at most four disjoint rectangles: full width bands above and below
+    /// the overlap, then the pieces left and right of it.
+    pub fn subtract(&self, other: &Rectangle) -> Vec<Rectangle> {
+        let Some(overlap) = self.intersection(other) else {
+            return if self.is_empty() { vec![] } else { vec![*self] };
+        };
+        [
+            Self::new(self.top_left, (overlap.top_left.x, self.bottom_right.y)),
+            Self::new((overlap.bottom_right.x, self.top_left.y), self.bottom_right),
+            Self::new(
+                (overlap.top_left.x, self.top_left.y),
+                (overlap.bottom_right.x, overlap.top_left.y),
+            ),
+            Self::new(
+                (overlap.top_left.x, overlap.bottom_right.y),
+                (overlap.bottom_right.x, self.bottom_right.y),
+            ),
+        ]
+        .into_iter()
+        .filter(|piece| !piece.is_empty())
+        .collect()
+    }
+
+    /// The part of the rectangle inside a grid with the given exclusive bounds, such as `Grid::bounds`.
+    pub fn clamp(&self, bounds: impl Into<GridPoint>) -> Rectangle {
+        let bounds = bounds.into();
+        let bottom_right = GridPoint::new(
+            self.bottom_right.x.min(bounds.x),
+            self.bottom_right.y.min(bounds.y),
+        );
+        let top_left = GridPoint::new(
+            self.top_left.x.min(bottom_right.x),
+            self.top_left.y.min(bottom_right.y),
+        );
+        Self::new(top_left, bottom_right)
+    }
+}
+
+impl From<(GridPoint, GridPoint)> for Rectangle {
+    fn from((top_left, bottom_right): (GridPoint, GridPoint)) -> Self {
+        Self {
+            top_left,
+            bottom_right,
+        }
+    }
+}
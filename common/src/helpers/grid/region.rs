@@ -0,0 +1,201 @@
+/// Connected regions of a Grid.
+/// Responsibilities:
+/// - Flood fill from a single point
+/// - Connected-component labelling of the whole grid
+/// - Region measurements (area, perimeter, sides, bounding box)
+use super::error::Result;
+use super::{Grid, GridPoint, Rectangle};
+use crate::helpers::direction::{
+    CardinalDirections, DiagonalDirections, DirectionBehaviour, RotationBehaviour,
+};
+use std::collections::VecDeque;
+
+/// A set of connected cells.
+#[derive(Debug, Clone)]
+pub struct Region {
+    pub label: usize,
+    pub points: Vec<GridPoint>,
+    perimeter: usize,
+    corners: usize,
+    bounds: Rectangle,
+}
+
+impl Region {
+    fn measure(
+        label: usize,
+        points: Vec<GridPoint>,
+        bounds: GridPoint,
+        labels: &Grid<Option<usize>>,
+    ) -> Self {
+        let contains = |point: Option<GridPoint>| {
+            point.is_some_and(|p| p.check_bounds(&bounds) && labels[p] == Some(label))
+        };
+
+        let mut perimeter = 0;
+        let mut corners = 0;
+        for point in &points {
+            for &direction in CardinalDirections::iter_all() {
+                let side = contains(direction.next_point(point));
+                let next_side = contains(direction.rotate_90().next_point(point));
+                // The diagonal sitting between `direction` and the next cardinal clockwise from it.
+                let diagonal =
+                    contains(DiagonalDirections::ALL[direction.as_index()].next_point(point));
+
+                perimeter += !side as usize;
+                // Convex corner when both sides are open, concave when both are closed but the diagonal is open.
+                corners += (!side && !next_side || side && next_side && !diagonal) as usize;
+            }
+        }
+
+        let (min, max) = points.iter().fold((points[0], points[0]), |(min, max), p| {
+            (
+                (min.x.min(p.x), min.y.min(p.y)).into(),
+                (max.x.max(p.x), max.y.max(p.y)).into(),
+            )
+        });
+
+        Self {
+            label,
+            points,
+            perimeter,
+            corners,
+            bounds: Rectangle::new(min, (max.x + 1, max.y + 1)),
+        }
+    }
+
+    /// Number of cells in the region.
+    pub fn area(&self) -> usize {
+        self.points.len()
+    }
+
+    /// Number of cell edges that border a cell outside the region, or the edge of the grid.
+    pub fn perimeter(&self) -> usize {
+        self.perimeter
+    }
+
+    /// Number of corners of the region's outline, including those of any holes.
+    pub fn corners(&self) -> usize {
+        self.corners
+    }
+
+    /// Number of straight sides of the region's outline. Always equal to the number of corners.
+    pub fn sides(&self) -> usize {
+        self.corners
+    }
+
+    /// Smallest rectangle containing every cell of the region, `bottom_right` exclusive.
+    pub fn bounding_box(&self) -> Rectangle {
+        self.bounds
+    }
+}
+
+/// Every connected region of a grid, with a label for each cell.
+#[derive(Debug, Clone)]
+pub struct Regions {
+    /// The label of the region each cell belongs to, an index into `regions`.
+    pub labels: Grid<usize>,
+    pub regions: Vec<Region>,
+}
+
+impl Regions {
+    /// The region containing `point`.
+    pub fn region_at(&self, point: GridPoint) -> Option<&Region> {
+        self.labels
+            .get_ref(point)
+            .ok()
+            .map(|&label| &self.regions[label])
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &Region> {
+        self.regions.iter()
+    }
+
+    pub fn len(&self) -> usize {
+        self.regions.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.regions.is_empty()
+    }
+}
+
+impl<T> Grid<T> {
+    fn fill<D>(
+        &self,
+        start: GridPoint,
+        label: usize,
+        labels: &mut Grid<Option<usize>>,
+        connected: &impl Fn(&T, &T) -> bool,
+    ) -> Vec<GridPoint>
+    where
+        D: DirectionBehaviour + 'static,
+    {
+        labels[start] = Some(label);
+        let mut points = vec![start];
+        let mut queue = VecDeque::from([start]);
+        while let Some(point) = queue.pop_front() {
+            for (next, value) in self.neighbours::<D>(point) {
+                if labels[next].is_none() && connected(&self[point], value) {
+                    labels[next] = Some(label);
+                    points.push(next);
+                    queue.push_back(next);
+                }
+            }
+        }
+        points
+    }
+
+    /// Flood fills from `start` over the neighbourhood `D`, stepping between neighbouring cells for which `connected` holds.
+    pub fn flood_fill<D>(
+        &self,
+        start: GridPoint,
+        connected: impl Fn(&T, &T) -> bool,
+    ) -> Result<Region>
+    where
+        D: DirectionBehaviour + 'static,
+    {
+        let start = self.validate_point(start)?;
+        let mut labels = Grid::new(self.rows, self.cols, None);
+        let points = self.fill::<D>(start, 0, &mut labels, &connected);
+        Ok(Region::measure(0, points, self.bounds(), &labels))
+    }
+
+    /// Labels every connected component over the neighbourhood `D`, where neighbouring cells are joined if `connected` holds.
+    pub fn label_regions<D>(&self, connected: impl Fn(&T, &T) -> bool) -> Regions
+    where
+        D: DirectionBehaviour + 'static,
+    {
+        let mut labels = Grid::new(self.rows, self.cols, None);
+        let mut groups = Vec::new();
+        for idx in 0..self.data.len() {
+            if labels.data[idx].is_none() {
+                let start = self.idx_to_point(idx);
+                groups.push(self.fill::<D>(start, groups.len(), &mut labels, &connected));
+            }
+        }
+
+        let regions = groups
+            .into_iter()
+            .enumerate()
+            .map(|(label, points)| Region::measure(label, points, self.bounds(), &labels))
+            .collect();
+        Regions {
+            labels: Grid {
+                data: labels.data.into_iter().map(Option::unwrap).collect(),
+                rows: self.rows,
+                cols: self.cols,
+            },
+            regions,
+        }
+    }
+}
+
+impl<T: PartialEq> Grid<T> {
+    /// Labels every connected component of equal values over the neighbourhood `D`.
+    pub fn regions<D>(&self) -> Regions
+    where
+        D: DirectionBehaviour + 'static,
+    {
+        self.label_regions::<D>(|a, b| a == b)
+    }
+}
@@ -0,0 +1,163 @@
+/// Path finding over a Grid.
+/// Responsibilities:
+/// - Breadth-first search for uniform step costs
+/// - Dijkstra for weighted steps
+/// - A* for a single target with a heuristic
+/// - Distance maps, predecessor trees and path reconstruction
+use super::error::Result;
+use super::{Grid, GridPoint};
+use crate::helpers::direction::DirectionBehaviour;
+use num_traits::PrimInt;
+use std::cmp::Reverse;
+use std::collections::{BinaryHeap, VecDeque};
+
+/// The outcome of a search from a single start point.
+#[derive(Debug, Clone)]
+pub struct SearchResult<C> {
+    pub start: GridPoint,
+    /// Distance from `start` to every reached cell, `None` if it was never reached.
+    pub distances: Grid<Option<C>>,
+    /// The cell each reached cell was reached from, `None` for `start` and unreached cells.
+    pub predecessors: Grid<Option<GridPoint>>,
+}
+
+impl<C: Copy> SearchResult<C> {
+    fn new(start: GridPoint, rows: usize, cols: usize) -> Self {
+        Self {
+            start,
+            distances: Grid::new(rows, cols, None),
+            predecessors: Grid::new(rows, cols, None),
+        }
+    }
+
+    /// Distance from `start` to `point`, if it was reached.
+    pub fn distance(&self, point: GridPoint) -> Option<C> {
+        self.distances.get_ref(point).ok().copied().flatten()
+    }
+
+    /// Walks the predecessor tree back from `target`, returning the path from `start` to `target` inclusive.
+    pub fn path_to(&self, target: GridPoint) -> Option<Vec<GridPoint>> {
+        self.distance(target)?;
+        let mut path = vec![target];
+        let mut current = target;
+        while let Some(previous) = self.predecessors[current] {
+            path.push(previous);
+            current = previous;
+        }
+        path.reverse();
+        Some(path)
+    }
+
+    /// Every reached cell along with its distance.
+    pub fn reached(&self) -> impl Iterator<Item = (GridPoint, C)> + '_ {
+        self.distances
+            .data
+            .iter()
+            .enumerate()
+            .filter_map(|(idx, d)| d.map(|d| (self.distances.idx_to_point(idx), d)))
+    }
+}
+
+impl<T> Grid<T> {
+    /// Breadth-first search from `start` over the neighbourhood `D`.
+    /// `passable` receives the current cell and a candidate neighbour and decides whether the step is allowed.
+    pub fn bfs<D>(
+        &self,
+        start: GridPoint,
+        passable: impl Fn((GridPoint, &T), (GridPoint, &T)) -> bool,
+    ) -> Result<SearchResult<usize>>
+    where
+        D: DirectionBehaviour + 'static,
+    {
+        let start = self.validate_point(start)?;
+        let mut result = SearchResult::new(start, self.rows, self.cols);
+        result.distances[start] = Some(0);
+
+        let mut queue = VecDeque::from([start]);
+        while let Some(point) = queue.pop_front() {
+            let distance = result.distances[point].unwrap();
+            let current = (point, &self[point]);
+            for next in self.neighbours::<D>(point) {
+                if result.distances[next.0].is_some() || !passable(current, next) {
+                    continue;
+                }
+                result.distances[next.0] = Some(distance + 1);
+                result.predecessors[next.0] = Some(point);
+                queue.push_back(next.0);
+            }
+        }
+        Ok(result)
+    }
+
+    /// Dijkstra's algorithm from `start` over the neighbourhood `D`.
+    /// `cost` returns the cost of stepping from the current cell to a neighbour, or `None` if the step is not allowed.
+    pub fn dijkstra<D, C>(
+        &self,
+        start: GridPoint,
+        cost: impl Fn((GridPoint, &T), (GridPoint, &T)) -> Option<C>,
+    ) -> Result<SearchResult<C>>
+    where
+        D: DirectionBehaviour + 'static,
+        C: PrimInt,
+    {
+        self.weighted_search::<D, C>(start, None, cost, |_| C::zero())
+    }
+
+    /// A* search from `start` to `goal` over the neighbourhood `D`.
+    /// `heuristic` must never overestimate the remaining cost to `goal`. The search stops once `goal` is settled,
+    /// so the distance map only covers the cells explored along the way.
+    pub fn astar<D, C>(
+        &self,
+        start: GridPoint,
+        goal: GridPoint,
+        cost: impl Fn((GridPoint, &T), (GridPoint, &T)) -> Option<C>,
+        heuristic: impl Fn(GridPoint) -> C,
+    ) -> Result<SearchResult<C>>
+    where
+        D: DirectionBehaviour + 'static,
+        C: PrimInt,
+    {
+        let goal = self.validate_point(goal)?;
+        self.weighted_search::<D, C>(start, Some(goal), cost, heuristic)
+    }
+
+    fn weighted_search<D, C>(
+        &self,
+        start: GridPoint,
+        goal: Option<GridPoint>,
+        cost: impl Fn((GridPoint, &T), (GridPoint, &T)) -> Option<C>,
+        heuristic: impl Fn(GridPoint) -> C,
+    ) -> Result<SearchResult<C>>
+    where
+        D: DirectionBehaviour + 'static,
+        C: PrimInt,
+    {
+        let start = self.validate_point(start)?;
+        let mut result = SearchResult::new(start, self.rows, self.cols);
+        result.distances[start] = Some(C::zero());
+
+        let mut heap = BinaryHeap::from([Reverse((heuristic(start), C::zero(), start))]);
+        while let Some(Reverse((_, distance, point))) = heap.pop() {
+            if result.distances[point].is_some_and(|best| best < distance) {
+                continue;
+            }
+            if goal == Some(point) {
+                break;
+            }
+            let current = (point, &self[point]);
+            for next in self.neighbours::<D>(point) {
+                let Some(step) = cost(current, next) else {
+                    continue;
+                };
+                let candidate = distance + step;
+                if result.distances[next.0].is_some_and(|best| best <= candidate) {
+                    continue;
+                }
+                result.distances[next.0] = Some(candidate);
+                result.predecessors[next.0] = Some(point);
+                heap.push(Reverse((candidate + heuristic(next.0), candidate, next.0)));
+            }
+        }
+        Ok(result)
+    }
+}
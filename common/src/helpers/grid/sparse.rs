@@ -0,0 +1,360 @@
+/// An unbounded 2D grid containing values of type T at signed coordinates.
+/// Responsibilities:
+/// - Value storage and access for arbitrary WorldPoints
+/// - Tracking the bounding box of occupied cells
+/// - Neighbourhood and search operations matching Grid
+/// - Conversion to and from a dense Grid
+use super::error::{GridError, Result};
+use super::{Grid, GridPoint, WorldPoint};
+use crate::helpers::direction::DirectionBehaviour;
+use num_traits::PrimInt;
+use std::cmp::Reverse;
+use std::collections::{BinaryHeap, HashMap, VecDeque};
+use std::fmt;
+use std::ops::Index;
+
+#[derive(Debug, Clone)]
+pub struct SparseGrid<T> {
+    data: HashMap<WorldPoint, T>,
+    /// Inclusive (min, max) corners of the occupied cells, `None` when empty.
+    bounds: Option<(WorldPoint, WorldPoint)>,
+}
+
+impl<T> Default for SparseGrid<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T> SparseGrid<T> {
+    pub fn new() -> Self {
+        Self {
+            data: HashMap::new(),
+            bounds: None,
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.data.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.data.is_empty()
+    }
+
+    pub fn contains(&self, point: WorldPoint) -> bool {
+        self.data.contains_key(&point)
+    }
+
+    pub fn get_ref(&self, point: WorldPoint) -> Option<&T> {
+        self.data.get(&point)
+    }
+
+    pub fn get_mut(&mut self, point: WorldPoint) -> Option<&mut T> {
+        self.data.get_mut(&point)
+    }
+
+    /// Sets the value at `point`, returning the previous value if there was one.
+    pub fn insert(&mut self, point: WorldPoint, val: T) -> Option<T> {
+        self.bounds = Some(match self.bounds {
+            Some((min, max)) => (
+                WorldPoint::new(min.x.min(point.x), min.y.min(point.y)),
+                WorldPoint::new(max.x.max(point.x), max.y.max(point.y)),
+            ),
+            None => (point, point),
+        });
+        self.data.insert(point, val)
+    }
+
+    /// Removes the value at `point`. The bounding box is recalculated if `point` was on its edge.
+    pub fn remove(&mut self, point: WorldPoint) -> Option<T> {
+        let removed = self.data.remove(&point)?;
+        if let Some((min, max)) = self.bounds {
+            if point.x == min.x || point.y == min.y || point.x == max.x || point.y == max.y {
+                self.recalculate_bounds();
+            }
+        }
+        Some(removed)
+    }
+
+    fn recalculate_bounds(&mut self) {
+        self.bounds = self.data.keys().fold(None, |bounds, &p| match bounds {
+            Some((min, max)) => Some((
+                WorldPoint::new(min.x.min(p.x), min.y.min(p.y)),
+                WorldPoint::new(max.x.max(p.x), max.y.max(p.y)),
+            )),
+            None => Some((p, p)),
+        });
+    }
+
+    /// Inclusive (min, max) corners of the occupied cells, `None` when empty.
+    pub fn bounding_box(&self) -> Option<(WorldPoint, WorldPoint)> {
+        self.bounds
+    }
+
+    /// Number of (rows, cols) covered by the bounding box.
+    pub fn dimensions(&self) -> GridPoint {
+        self.bounds
+            .map(|(min, max)| {
+                GridPoint::new(
+                    max.x.abs_diff(min.x) as usize + 1,
+                    max.y.abs_diff(min.y) as usize + 1,
+                )
+            })
+            .unwrap_or_else(GridPoint::zero)
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (WorldPoint, &T)> + '_ {
+        self.data.iter().map(|(p, v)| (*p, v))
+    }
+
+    /// Occupied neighbours of a point for the given neighbourhood, e.g. `CardinalDirections`.
+    pub fn neighbours<D>(&self, point: WorldPoint) -> impl Iterator<Item = (WorldPoint, &T)> + '_
+    where
+        D: DirectionBehaviour + 'static,
+    {
+        D::iter_all().filter_map(move |&direction| {
+            let next = point.move_in(direction);
+            self.data.get(&next).map(|v| (next, v))
+        })
+    }
+
+    /// Breadth-first search from `start` over occupied cells in the neighbourhood `D`.
+    /// `passable` receives the current cell and a candidate neighbour and decides whether the step is allowed.
+    pub fn bfs<D>(
+        &self,
+        start: WorldPoint,
+        passable: impl Fn((WorldPoint, &T), (WorldPoint, &T)) -> bool,
+    ) -> SparseSearchResult<usize>
+    where
+        D: DirectionBehaviour + 'static,
+    {
+        let mut result = SparseSearchResult::new(start);
+        let Some(value) = self.data.get(&start) else {
+            return result;
+        };
+        result.distances.insert(start, 0);
+
+        let mut queue = VecDeque::from([(start, value)]);
+        while let Some(current) = queue.pop_front() {
+            let distance = result.distances[&current.0];
+            for next in self.neighbours::<D>(current.0) {
+                if result.distances.contains_key(&next.0) || !passable(current, next) {
+                    continue;
+                }
+                result.distances.insert(next.0, distance + 1);
+                result.predecessors.insert(next.0, current.0);
+                queue.push_back(next);
+            }
+        }
+        result
+    }
+
+    /// Dijkstra's algorithm from `start` over occupied cells in the neighbourhood `D`.
+    /// `cost` returns the cost of stepping from the current cell to a neighbour, or `None` if the step is not allowed.
+    pub fn dijkstra<D, C>(
+        &self,
+        start: WorldPoint,
+        cost: impl Fn((WorldPoint, &T), (WorldPoint, &T)) -> Option<C>,
+    ) -> SparseSearchResult<C>
+    where
+        D: DirectionBehaviour + 'static,
+        C: PrimInt,
+    {
+        self.weighted_search::<D, C>(start, None, cost, |_| C::zero())
+    }
+
+    /// A* search from `start` to `goal` over occupied cells in the neighbourhood `D`.
+    /// `heuristic` must never overestimate the remaining cost to `goal`. The search stops once `goal` is settled,
+    /// so the distance map only covers the cells explored along the way.
+    pub fn astar<D, C>(
+        &self,
+        start: WorldPoint,
+        goal: WorldPoint,
+        cost: impl Fn((WorldPoint, &T), (WorldPoint, &T)) -> Option<C>,
+        heuristic: impl Fn(WorldPoint) -> C,
+    ) -> SparseSearchResult<C>
+    where
+        D: DirectionBehaviour + 'static,
+        C: PrimInt,
+    {
+        self.weighted_search::<D, C>(start, Some(goal), cost, heuristic)
+    }
+
+    fn weighted_search<D, C>(
+        &self,
+        start: WorldPoint,
+        goal: Option<WorldPoint>,
+        cost: impl Fn((WorldPoint, &T), (WorldPoint, &T)) -> Option<C>,
+        heuristic: impl Fn(WorldPoint) -> C,
+    ) -> SparseSearchResult<C>
+    where
+        D: DirectionBehaviour + 'static,
+        C: PrimInt,
+    {
+        let mut result = SparseSearchResult::new(start);
+        if !self.data.contains_key(&start) {
+            return result;
+        }
+        result.distances.insert(start, C::zero());
+
+        let mut heap = BinaryHeap::from([Reverse((heuristic(start), C::zero(), start))]);
+        while let Some(Reverse((_, distance, point))) = heap.pop() {
+            if result.distances[&point] < distance {
+                continue;
+            }
+            if goal == Some(point) {
+                break;
+            }
+            let current = (point, &self.data[&point]);
+            for next in self.neighbours::<D>(point) {
+                let Some(step) = cost(current, next) else {
+                    continue;
+                };
+                let candidate = distance + step;
+                if result
+                    .distances
+                    .get(&next.0)
+                    .is_some_and(|&best| best <= candidate)
+                {
+                    continue;
+                }
+                result.distances.insert(next.0, candidate);
+                result.predecessors.insert(next.0, point);
+                heap.push(Reverse((candidate + heuristic(next.0), candidate, next.0)));
+            }
+        }
+        result
+    }
+}
+
+/// The outcome of a search over a SparseGrid from a single start point.
+#[derive(Debug, Clone)]
+pub struct SparseSearchResult<C> {
+    pub start: WorldPoint,
+    /// Distance from `start` to every reached cell.
+    pub distances: HashMap<WorldPoint, C>,
+    /// The cell each reached cell was reached from. `start` has no entry.
+    pub predecessors: HashMap<WorldPoint, WorldPoint>,
+}
+
+impl<C: Copy> SparseSearchResult<C> {
+    fn new(start: WorldPoint) -> Self {
+        Self {
+            start,
+            distances: HashMap::new(),
+            predecessors: HashMap::new(),
+        }
+    }
+
+    /// Distance from `start` to `point`, if it was reached.
+    pub fn distance(&self, point: WorldPoint) -> Option<C> {
+        self.distances.get(&point).copied()
+    }
+
+    /// Walks the predecessor tree back from `target`, returning the path from `start` to `target` inclusive.
+    pub fn path_to(&self, target: WorldPoint) -> Option<Vec<WorldPoint>> {
+        self.distance(target)?;
+        let mut path = vec![target];
+        let mut current = target;
+        while let Some(&previous) = self.predecessors.get(&current) {
+            path.push(previous);
+            current = previous;
+        }
+        path.reverse();
+        Some(path)
+    }
+
+    /// Every reached cell along with its distance.
+    pub fn reached(&self) -> impl Iterator<Item = (WorldPoint, C)> + '_ {
+        self.distances.iter().map(|(&point, &d)| (point, d))
+    }
+}
+
+impl<T: PartialEq> SparseGrid<T> {
+    pub fn search(&self, needle: T) -> Option<WorldPoint> {
+        self.data
+            .iter()
+            .find_map(|(p, v)| (*v == needle).then_some(*p))
+    }
+
+    pub fn search_all(&self, needle: T) -> Vec<WorldPoint> {
+        self.data
+            .iter()
+            .filter_map(|(p, v)| (*v == needle).then_some(*p))
+            .collect()
+    }
+}
+
+impl<T: Clone> SparseGrid<T> {
+    /// Builds a sparse grid from the cells of `grid` for which `keep` holds.
+    /// Cell (row, col) is stored at `offset + (row, col)`.
+    pub fn from_grid(
+        grid: &Grid<T>,
+        offset: WorldPoint,
+        keep: impl Fn(&T) -> bool,
+    ) -> Result<Self> {
+        let mut sparse = Self::new();
+        for (idx, val) in grid.data.iter().enumerate() {
+            if keep(val) {
+                let point = grid
+                    .idx_to_point(idx)
+                    .try_cast::<i32>()
+                    .map_err(GridError::conversion)?;
+                sparse.insert(offset + point, val.clone());
+            }
+        }
+        Ok(sparse)
+    }
+
+    /// Builds a dense grid covering the bounding box, filling unoccupied cells with `default`.
+    /// Returns the grid along with the offset of its (0, 0) cell in world coordinates.
+    pub fn to_grid(&self, default: T) -> Result<(Grid<T>, WorldPoint)> {
+        let (min, _) = self
+            .bounds
+            .ok_or_else(|| GridError::operation("Sparse grid is empty"))?;
+        let GridPoint { x: rows, y: cols } = self.dimensions();
+        let mut grid = Grid::build_default(rows, cols, default)?;
+        for (point, val) in &self.data {
+            grid.set(*point - min, val.clone());
+        }
+        Ok((grid, min))
+    }
+}
+
+impl<T> Index<WorldPoint> for SparseGrid<T> {
+    type Output = T;
+
+    fn index(&self, point: WorldPoint) -> &Self::Output {
+        &self.data[&point]
+    }
+}
+
+impl<T> FromIterator<(WorldPoint, T)> for SparseGrid<T> {
+    fn from_iter<I: IntoIterator<Item = (WorldPoint, T)>>(iter: I) -> Self {
+        let mut sparse = Self::new();
+        for (point, val) in iter {
+            sparse.insert(point, val);
+        }
+        sparse
+    }
+}
+
+impl<T: fmt::Display> fmt::Display for SparseGrid<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let Some((min, max)) = self.bounds else {
+            return Ok(());
+        };
+        for i in min.x..=max.x {
+            write!(f, "Row {} |", i)?;
+            for j in min.y..=max.y {
+                match self.data.get(&WorldPoint::new(i, j)) {
+                    Some(val) => write!(f, " {} |", val)?,
+                    None => write!(f, " . |")?,
+                }
+            }
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,192 @@
+/// Geometric transforms of a Grid.
+/// Responsibilities:
+/// - The eight rotations and reflections of a rectangle
+/// - Composition and inversion of transforms
+/// - Mapping points between original and transformed grids
+/// - In-place and copying transforms of grid data
+use super::{Grid, GridPoint};
+
+/// One of the eight symmetries of a rectangle. Rotations are clockwise.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub enum Transform {
+    Identity,
+    Rotate90,
+    Rotate180,
+    Rotate270,
+    /// Mirror left to right, columns are reversed.
+    FlipHorizontal,
+    /// Mirror top to bottom, rows are reversed.
+    FlipVertical,
+    /// Mirror across the main diagonal, rows become columns.
+    Transpose,
+    /// Mirror across the anti-diagonal.
+    AntiTranspose,
+}
+
+impl Transform {
+    pub const ALL: [Self; 8] = [
+        Transform::Identity,
+        Transform::Rotate90,
+        Transform::Rotate180,
+        Transform::Rotate270,
+        Transform::FlipHorizontal,
+        Transform::FlipVertical,
+        Transform::Transpose,
+        Transform::AntiTranspose,
+    ];
+
+    /// Every transform is a row flip, then a column flip, then a transpose, each optional.
+    /// Ordered as (flip rows, flip columns, transpose).
+    fn as_parts(&self) -> (bool, bool, bool) {
+        match self {
+            Self::Identity => (false, false, false),
+            Self::Rotate90 => (true, false, true),
+            Self::Rotate180 => (true, true, false),
+            Self::Rotate270 => (false, true, true),
+            Self::FlipHorizontal => (false, true, false),
+            Self::FlipVertical => (true, false, false),
+            Self::Transpose => (false, false, true),
+            Self::AntiTranspose => (true, true, true),
+        }
+    }
+
+    fn from_parts(parts: (bool, bool, bool)) -> Self {
+        *Self::ALL.iter().find(|t| t.as_parts() == parts).unwrap()
+    }
+
+    /// The transform that undoes this one.
+    pub fn inverse(&self) -> Self {
+        match self {
+            Self::Rotate90 => Self::Rotate270,
+            Self::Rotate270 => Self::Rotate90,
+            other => *other,
+        }
+    }
+
+    /// The transform equivalent to applying `self` and then `other`.
+    pub fn then(&self, other: Self) -> Self {
+        let (rows_a, cols_a, transpose_a) = self.as_parts();
+        let (rows_b, cols_b, transpose_b) = other.as_parts();
+        // Once transposed, flipping rows afterwards flips the original columns and vice versa.
+        let (rows_b, cols_b) = if transpose_a {
+            (cols_b, rows_b)
+        } else {
+            (rows_b, cols_b)
+        };
+        Self::from_parts((rows_a ^ rows_b, cols_a ^ cols_b, transpose_a ^ transpose_b))
+    }
+
+    /// Whether rows and columns swap places.
+    pub fn swaps_axes(&self) -> bool {
+        self.as_parts().2
+    }
+
+    /// The bounds of a grid with `bounds` once transformed.
+    pub fn bounds(&self, bounds: GridPoint) -> GridPoint {
+        if self.swaps_axes() {
+            GridPoint::new(bounds.y, bounds.x)
+        } else {
+            bounds
+        }
+    }
+
+    /// Maps a point in a grid with `bounds` to where it ends up in the transformed grid.
+    pub fn to_transformed(self, point: GridPoint, bounds: GridPoint) -> GridPoint {
+        let (flip_rows, flip_cols, transpose) = self.as_parts();
+        let x = if flip_rows {
+            bounds.x - 1 - point.x
+        } else {
+            point.x
+        };
+        let y = if flip_cols {
+            bounds.y - 1 - point.y
+        } else {
+            point.y
+        };
+        if transpose {
+            GridPoint::new(y, x)
+        } else {
+            GridPoint::new(x, y)
+        }
+    }
+
+    /// Maps a point in the transformed grid back to the original grid with `bounds`.
+    pub fn to_original(self, point: GridPoint, bounds: GridPoint) -> GridPoint {
+        self.inverse().to_transformed(point, self.bounds(bounds))
+    }
+}
+
+impl<T> Grid<T> {
+    /// Applies `transform` to the grid without cloning any values, following each permutation cycle in turn.
+    pub fn transform(&mut self, transform: Transform) {
+        let bounds = self.bounds();
+        let new_bounds = transform.bounds(bounds);
+        let destination = |idx: usize| {
+            let point = GridPoint::new(idx / bounds.y, idx % bounds.y);
+            let point = transform.to_transformed(point, bounds);
+            point.x * new_bounds.y + point.y
+        };
+
+        let mut visited = vec![false; self.data.len()];
+        for start in 0..self.data.len() {
+            if visited[start] {
+                continue;
+            }
+            visited[start] = true;
+            let mut idx = destination(start);
+            while idx != start {
+                self.data.swap(start, idx);
+                visited[idx] = true;
+                idx = destination(idx);
+            }
+        }
+        self.rows = new_bounds.x;
+        self.cols = new_bounds.y;
+    }
+
+    /// A transformed copy of the grid.
+    pub fn transformed(&self, transform: Transform) -> Grid<T>
+    where
+        T: Clone,
+    {
+        let bounds = transform.bounds(self.bounds());
+        let data = (0..self.data.len())
+            .map(|idx| {
+                let point = transform.to_original(
+                    GridPoint::new(idx / bounds.y, idx % bounds.y),
+                    self.bounds(),
+                );
+                self[point].clone()
+            })
+            .collect();
+        Grid {
+            data,
+            rows: bounds.x,
+            cols: bounds.y,
+        }
+    }
+
+    pub fn rotate_clockwise(&mut self) {
+        self.transform(Transform::Rotate90);
+    }
+
+    pub fn rotate_counter_clockwise(&mut self) {
+        self.transform(Transform::Rotate270);
+    }
+
+    pub fn rotate_180(&mut self) {
+        self.transform(Transform::Rotate180);
+    }
+
+    pub fn transpose(&mut self) {
+        self.transform(Transform::Transpose);
+    }
+
+    pub fn flip_horizontal(&mut self) {
+        self.transform(Transform::FlipHorizontal);
+    }
+
+    pub fn flip_vertical(&mut self) {
+        self.transform(Transform::FlipVertical);
+    }
+}
@@ -0,0 +1,151 @@
+/// A Grid whose edges wrap around, as on a torus.
+/// Responsibilities:
+/// - Wrap-around value access for any signed or unsigned point
+/// - Wrapping directional movement and neighbourhood operations
+/// - Constant time movement by a velocity over many steps
+use super::{Grid, GridPoint, WorldPoint};
+use crate::helpers::direction::{DirectionBehaviour, DirectionalMove};
+use crate::helpers::point::Point;
+use num_traits::ToPrimitive;
+use std::ops::{Deref, DerefMut, Index, IndexMut};
+
+#[derive(Debug, Clone)]
+pub struct WrappingGrid<T> {
+    grid: Grid<T>,
+}
+
+impl<T> WrappingGrid<T> {
+    pub fn new(grid: Grid<T>) -> Self {
+        Self { grid }
+    }
+
+    pub fn into_inner(self) -> Grid<T> {
+        self.grid
+    }
+
+    /// Maps any point onto the grid, wrapping around the edges.
+    pub fn wrap<P>(&self, point: Point<P>) -> GridPoint
+    where
+        P: ToPrimitive + Copy,
+    {
+        point
+            .wrapped_as(&self.grid.bounds())
+            .expect("grid should not be empty and point coordinates should fit in an i128")
+    }
+
+    pub fn get_ref<P>(&self, point: Point<P>) -> &T
+    where
+        P: ToPrimitive + Copy,
+    {
+        &self.grid.data[self.wrapped_index(point)]
+    }
+
+    pub fn set<P>(&mut self, point: Point<P>, val: T)
+    where
+        P: ToPrimitive + Copy,
+    {
+        let idx = self.wrapped_index(point);
+        self.grid.data[idx] = val;
+    }
+
+    fn wrapped_index<P>(&self, point: Point<P>) -> usize
+    where
+        P: ToPrimitive + Copy,
+    {
+        let point = self.wrap(point);
+        point.x * self.grid.cols + point.y
+    }
+
+    pub fn step_in_direction<P, D>(&self, mover: DirectionalMove<P, D>) -> GridPoint
+    where
+        D: DirectionBehaviour,
+        P: ToPrimitive + Copy,
+    {
+        let delta = mover
+            .direction
+            .as_delta::<i128>()
+            .scale(mover.steps as i128);
+        let point = mover
+            .point
+            .try_cast::<i128>()
+            .expect("point should fit in an i128");
+        self.wrap(point + delta)
+    }
+
+    pub fn value_in_direction<P, D>(&self, mover: DirectionalMove<P, D>) -> &T
+    where
+        D: DirectionBehaviour,
+        P: ToPrimitive + Copy,
+    {
+        &self[self.step_in_direction(mover)]
+    }
+
+    /// The position reached from `point` after moving by `velocity` for `steps` steps.
+    pub fn step_with_velocity<P>(
+        &self,
+        point: Point<P>,
+        velocity: WorldPoint,
+        steps: i64,
+    ) -> GridPoint
+    where
+        P: ToPrimitive + Copy,
+    {
+        let point = point
+            .try_cast::<i128>()
+            .expect("point should fit in an i128");
+        let velocity = velocity.map(i128::from).scale(i128::from(steps));
+        self.wrap(point + velocity)
+    }
+
+    /// Neighbours of a point for the given neighbourhood, wrapping around the edges.
+    pub fn neighbours<D>(&self, point: GridPoint) -> impl Iterator<Item = (GridPoint, &T)> + '_
+    where
+        D: DirectionBehaviour + 'static,
+    {
+        D::iter_all().map(move |&direction| {
+            let next = self.step_in_direction(DirectionalMove::new(point, direction));
+            (next, &self[next])
+        })
+    }
+}
+
+impl<T> From<Grid<T>> for WrappingGrid<T> {
+    fn from(grid: Grid<T>) -> Self {
+        Self::new(grid)
+    }
+}
+
+impl<T> Deref for WrappingGrid<T> {
+    type Target = Grid<T>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.grid
+    }
+}
+
+impl<T> DerefMut for WrappingGrid<T> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.grid
+    }
+}
+
+impl<T, P> Index<Point<P>> for WrappingGrid<T>
+where
+    P: ToPrimitive + Copy,
+{
+    type Output = T;
+
+    fn index(&self, point: Point<P>) -> &Self::Output {
+        self.get_ref(point)
+    }
+}
+
+impl<T, P> IndexMut<Point<P>> for WrappingGrid<T>
+where
+    P: ToPrimitive + Copy,
+{
+    fn index_mut(&mut self, point: Point<P>) -> &mut Self::Output {
+        let idx = self.wrapped_index(point);
+        &mut self.grid.data[idx]
+    }
+}
@@ -0,0 +1,287 @@
+/// Straight lines between integer points.
+/// Responsibilities:
+/// - Bresenham rasterisation, the cells a drawn line passes through
+/// - Exact lattice points on a segment using a gcd-reduced step
+/// - Segment-segment intersection, exact even when the crossing is not a lattice point
+/// - Extending a segment to the infinite line through it, clipped to a Rectangle
+use super::grid::Rectangle;
+use super::linear::Rational;
+use super::number_theory::gcd;
+use super::point::Point;
+use num_traits::{PrimInt, Signed};
+
+/// The cells a line from `start` to `end` passes through by Bresenham's algorithm, both ends included.
+/// Every step moves to one of the eight neighbouring cells.
+pub fn bresenham<P: PrimInt + Signed>(start: Point<P>, end: Point<P>) -> Bresenham<P> {
+    let delta = Point::new((end.x - start.x).abs(), -(end.y - start.y).abs());
+    Bresenham {
+        current: start,
+        end,
+        delta,
+        sign: Point::new((end.x - start.x).signum(), (end.y - start.y).signum()),
+        error: delta.x + delta.y,
+        done: false,
+    }
+}
+
+/// Iterator over the cells of a rasterised line. See [`bresenham`].
+#[derive(Debug, Clone)]
+pub struct Bresenham<P> {
+    current: Point<P>,
+    end: Point<P>,
+    /// (|dx|, -|dy|)
+    delta: Point<P>,
+    sign: Point<P>,
+    error: P,
+    done: bool,
+}
+
+impl<P: PrimInt + Signed> Iterator for Bresenham<P> {
+    type Item = Point<P>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.done {
+            return None;
+        }
+        let point = self.current;
+        if point == self.end {
+            self.done = true;
+            return Some(point);
+        }
+        let doubled = self.error + self.error;
+        if doubled >= self.delta.y {
+            self.error = self.error + self.delta.y;
+            self.current.x = self.current.x + self.sign.x;
+        }
+        if doubled <= self.delta.x {
+            self.error = self.error + self.delta.x;
+            self.current.y = self.current.y + self.sign.y;
+        }
+        Some(point)
+    }
+}
+
+/// Where two segments meet.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Intersection<P> {
+    /// A single shared point, which need not be a lattice point.
+    Point(Point<Rational>),
+    /// Collinear segments sharing more than one point.
+    Overlap(Segment<P>),
+}
+
+/// The straight segment from `start` to `end`, both included.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Segment<P> {
+    pub start: Point<P>,
+    pub end: Point<P>,
+}
+
+fn wide<P: PrimInt>(point: Point<P>) -> Point<i128> {
+    point.map(|coord| coord.to_i128().expect("coordinates fit in i128"))
+}
+
+fn cross(a: Point<i128>, b: Point<i128>) -> i128 {
+    a.x * b.y - a.y * b.x
+}
+
+fn dot(a: Point<i128>, b: Point<i128>) -> i128 {
+    a.x * b.x + a.y * b.y
+}
+
+impl<P: PrimInt + Signed> Segment<P> {
+    pub fn new(start: Point<P>, end: Point<P>) -> Self {
+        Self { start, end }
+    }
+
+    /// The vector from `start` to `end`.
+    pub fn delta(&self) -> Point<P> {
+        self.end - self.start
+    }
+
+    /// The shortest step between consecutive lattice points, zero for a segment that is a single point.
+    pub fn step(&self) -> Point<P> {
+        let delta = self.delta();
+        let divisor = gcd(delta.x, delta.y);
+        if divisor.is_zero() {
+            delta
+        } else {
+            Point::new(delta.x / divisor, delta.y / divisor)
+        }
+    }
+
+    /// Number of lattice points on the segment, including both ends.
+    pub fn lattice_count(&self) -> P {
+        let delta = self.delta();
+        gcd(delta.x, delta.y) + P::one()
+    }
+
+    /// Every lattice point exactly on the segment, from `start` to `end`.
+    pub fn lattice_points(&self) -> impl Iterator<Item = Point<P>> {
+        let (start, step) = (self.start, self.step());
+        let count = self.lattice_count().to_usize().unwrap_or(0);
+        (0..count).scan(start, move |current, _| {
+            let point = *current;
+            *current = *current + step;
+            Some(point)
+        })
+    }
+
+    /// The cells a drawn line passes through. See [`bresenham`].
+    pub fn rasterise(&self) -> Bresenham<P> {
+        bresenham(self.start, self.end)
+    }
+
+    pub fn contains(&self, point: Point<P>) -> bool {
+        let (start, end, point) = (wide(self.start), wide(self.end), wide(point));
+        cross(end - start, point - start) == 0
+            && start.x.min(end.x) <= point.x
+            && point.x <= start.x.max(end.x)
+            && start.y.min(end.y) <= point.y
+            && point.y <= start.y.max(end.y)
+    }
+
+    /// Where this segment meets `other`, if anywhere.
+    pub fn intersection(&self, other: &Self) -> Option<Intersection<P>> {
+        let (a, b, c, d) = (
+            wide(self.start),
+            wide(self.end),
+            wide(other.start),
+            wide(other.end),
+        );
+        let (r, s, offset) = (b - a, d - c, c - a);
+        let denominator = cross(r, s);
+        if denominator != 0 {
+            let (t, u) = (cross(offset, s), cross(offset, r));
+            let within = |value: i128| {
+                if denominator > 0 {
+                    0 <= value && value <= denominator
+                } else {
+                    denominator <= value && value <= 0
+                }
+            };
+            if !within(t) || !within(u) {
+                return None;
+            }
+            let t = Rational::new(t, denominator);
+            let along =
+                |origin: i128, extent: i128| Rational::from(origin) + Rational::from(extent) * t;
+            return Some(Intersection::Point(Point::new(
+                along(a.x, r.x),
+                along(a.y, r.y),
+            )));
+        }
+        if cross(offset, r) != 0 || cross(offset, s) != 0 {
+            return None;
+        }
+
+        // Collinear, or at least one segment is a single point: the shared part runs between ends of the two.
+        let axis = if r.is_origin() { s } else { r };
+        let mut shared: Vec<Point<P>> = [self.start, self.end, other.start, other.end]
+            .into_iter()
+            .filter(|&point| self.contains(point) && other.contains(point))
+            .collect();
+        shared.sort_by_key(|&point| dot(wide(point), axis));
+        let (&first, &last) = (shared.first()?, shared.last()?);
+        Some(if first == last {
+            Intersection::Point(Point::new(
+                Rational::from(wide(first).x),
+                Rational::from(wide(first).y),
+            ))
+        } else {
+            Intersection::Overlap(Segment::new(first, last))
+        })
+    }
+
+    /// The part of the infinite line through this segment that lies in `bounds`, running between the outermost
+    /// lattice points on the line inside it. A single point segment stays a single point.
+    /// `None` if the line misses `bounds` or an end does not fit in `P`.
+    pub fn extend_to(&self, bounds: &Rectangle) -> Option<Self> {
+        if bounds.is_empty() {
+            return None;
+        }
+        let (start, step) = (wide(self.start), wide(self.step()));
+        let low = wide(bounds.top_left);
+        let high = wide(bounds.bottom_right) - Point::new(1, 1);
+
+        // The range of t for which start + t * step stays within [low, high] on one axis.
+        let axis_range = |origin: i128, step: i128, low: i128, high: i128| {
+            if step == 0 {
+                (low <= origin && origin <= high).then_some((i128::MIN, i128::MAX))
+            } else if step > 0 {
+                Some((div_ceil(low - origin, step), div_floor(high - origin, step)))
+            } else {
+                Some((div_ceil(high - origin, step), div_floor(low - origin, step)))
+            }
+        };
+        let (x_low, x_high) = axis_range(start.x, step.x, low.x, high.x)?;
+        let (y_low, y_high) = axis_range(start.y, step.y, low.y, high.y)?;
+        let (t_low, t_high) = (x_low.max(y_low), x_high.min(y_high));
+        if t_low > t_high {
+            return None;
+        }
+        if step.is_origin() {
+            return Some(*self);
+        }
+        let at = |t: i128| {
+            let point = start + Point::new(step.x * t, step.y * t);
+            Some(Point::new(P::from(point.x)?, P::from(point.y)?))
+        };
+        Some(Self::new(at(t_low)?, at(t_high)?))
+    }
+}
+
+fn div_floor(a: i128, b: i128) -> i128 {
+    let quotient = a / b;
+    if a % b != 0 && ((a < 0) != (b < 0)) {
+        quotient - 1
+    } else {
+        quotient
+    }
+}
+
+fn div_ceil(a: i128, b: i128) -> i128 {
+    -div_floor(-a, b)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::helpers::grid::GridPoint;
+
+    #[test]
+    fn lattice_points_use_the_reduced_step() {
+        let segment = Segment::new(Point::new(0i32, 0), Point::new(4, 6));
+        assert_eq!(segment.step(), Point::new(2, 3));
+        assert_eq!(
+            segment.lattice_points().collect::<Vec<_>>(),
+            [Point::new(0, 0), Point::new(2, 3), Point::new(4, 6)]
+        );
+    }
+
+    #[test]
+    fn crossing_between_lattice_points() {
+        let a = Segment::new(Point::new(0i32, 0), Point::new(1, 1));
+        let b = Segment::new(Point::new(0, 1), Point::new(1, 0));
+        let half = Rational::new(1, 2);
+        assert_eq!(
+            a.intersection(&b),
+            Some(Intersection::Point(Point::new(half, half)))
+        );
+    }
+
+    #[test]
+    fn extend_to_bounds() {
+        let bounds = Rectangle::entire_grid(GridPoint::new(10, 10));
+        let segment = Segment::new(Point::new(2i32, 3), Point::new(4, 4));
+        assert_eq!(
+            segment.extend_to(&bounds),
+            Some(Segment::new(Point::new(0, 2), Point::new(8, 6)))
+        );
+
+        // The far end (199, 199) lies inside the bounds but not within i8.
+        let bounds = Rectangle::entire_grid(GridPoint::new(200, 200));
+        let diagonal = Segment::new(Point::new(0i8, 0), Point::new(1, 1));
+        assert_eq!(diagonal.extend_to(&bounds), None);
+    }
+}
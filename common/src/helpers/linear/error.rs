@@ -0,0 +1,28 @@
+use std::fmt;
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+pub enum LinearError {
+    #[error("Dimension error: {0}")]
+    DimensionError(String),
+    #[error("Search error: {0}")]
+    SearchError(String),
+    #[error("Overflow error: {0}")]
+    OverflowError(String),
+}
+
+pub type Result<T> = std::result::Result<T, LinearError>;
+
+impl LinearError {
+    pub fn dimension<M: fmt::Display>(message: M) -> Self {
+        Self::DimensionError(message.to_string())
+    }
+
+    pub fn search<M: fmt::Display>(message: M) -> Self {
+        Self::SearchError(message.to_string())
+    }
+
+    pub fn overflow<M: fmt::Display>(message: M) -> Self {
+        Self::OverflowError(message.to_string())
+    }
+}
@@ -0,0 +1,294 @@
+/// Linear algebra over GF(2), where addition is XOR.
+/// Responsibilities:
+/// - Packed bit vectors with XOR and bit counting
+/// - Gaussian elimination of XOR systems, with rank, consistency and free variables
+/// - Particular solutions, the null space and every solution in Gray code order
+/// - Minimum weight solutions by exhaustive search
+use super::error::{LinearError, Result};
+use std::fmt;
+use std::ops::{BitXor, BitXorAssign};
+
+const WORD_BITS: usize = u64::BITS as usize;
+
+/// A fixed length vector over GF(2), packed into u64 words.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct BitVector {
+    words: Vec<u64>,
+    len: usize,
+}
+
+impl BitVector {
+    pub fn new(len: usize) -> Self {
+        Self {
+            words: vec![0; len.div_ceil(WORD_BITS)],
+            len,
+        }
+    }
+
+    /// A vector of length `len` with the bits at `indices` set.
+    /// Panics if an index is out of range.
+    pub fn from_indices(len: usize, indices: impl IntoIterator<Item = usize>) -> Self {
+        let mut vector = Self::new(len);
+        for idx in indices {
+            vector.set(idx, true);
+        }
+        vector
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Panics if `idx` is out of range.
+    pub fn get(&self, idx: usize) -> bool {
+        assert!(idx < self.len, "bit {} out of range for {}", idx, self.len);
+        self.words[idx / WORD_BITS] >> (idx % WORD_BITS) & 1 == 1
+    }
+
+    /// Panics if `idx` is out of range.
+    pub fn set(&mut self, idx: usize, val: bool) {
+        assert!(idx < self.len, "bit {} out of range for {}", idx, self.len);
+        let mask = 1 << (idx % WORD_BITS);
+        if val {
+            self.words[idx / WORD_BITS] |= mask;
+        } else {
+            self.words[idx / WORD_BITS] &= !mask;
+        }
+    }
+
+    /// Panics if `idx` is out of range.
+    pub fn toggle(&mut self, idx: usize) {
+        assert!(idx < self.len, "bit {} out of range for {}", idx, self.len);
+        self.words[idx / WORD_BITS] ^= 1 << (idx % WORD_BITS);
+    }
+
+    pub fn count_ones(&self) -> usize {
+        self.words.iter().map(|w| w.count_ones() as usize).sum()
+    }
+
+    pub fn is_zero(&self) -> bool {
+        self.words.iter().all(|&w| w == 0)
+    }
+
+    /// Indices of the set bits, lowest first.
+    pub fn iter_ones(&self) -> impl Iterator<Item = usize> + '_ {
+        self.words.iter().enumerate().flat_map(|(idx, &word)| {
+            std::iter::successors((word != 0).then_some(word), |&w| {
+                let rest = w & (w - 1);
+                (rest != 0).then_some(rest)
+            })
+            .map(move |w| idx * WORD_BITS + w.trailing_zeros() as usize)
+        })
+    }
+}
+
+impl BitXorAssign<&BitVector> for BitVector {
+    /// Panics if the lengths differ.
+    fn bitxor_assign(&mut self, other: &BitVector) {
+        assert_eq!(self.len, other.len, "mismatched bit vector lengths");
+        self.words
+            .iter_mut()
+            .zip(&other.words)
+            .for_each(|(a, &b)| *a ^= b);
+    }
+}
+
+impl BitXor<&BitVector> for BitVector {
+    type Output = BitVector;
+
+    /// Panics if the lengths differ.
+    fn bitxor(mut self, other: &BitVector) -> Self::Output {
+        self ^= other;
+        self
+    }
+}
+
+impl fmt::Display for BitVector {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for idx in 0..self.len {
+            write!(f, "{}", if self.get(idx) { '1' } else { '0' })?;
+        }
+        Ok(())
+    }
+}
+
+/// A system of equations over GF(2), where addition is XOR.
+#[derive(Debug, Clone)]
+pub struct Gf2System {
+    /// One row per equation, with the right hand side stored in the bit after the coefficients.
+    rows: Vec<BitVector>,
+    columns: usize,
+}
+
+impl Gf2System {
+    /// One vector of coefficients per equation, along with the right hand side bit of each equation in `rhs`.
+    pub fn new(rows: &[BitVector], rhs: &BitVector) -> Result<Self> {
+        if rows.len() != rhs.len() {
+            return Err(LinearError::dimension(format!(
+                "{} equations but {} right hand sides",
+                rows.len(),
+                rhs.len()
+            )));
+        }
+        let columns = rows.first().map_or(0, BitVector::len);
+        if rows.iter().any(|row| row.len() != columns) {
+            return Err(LinearError::dimension("Inconsistent row lengths"));
+        }
+        let rows = rows
+            .iter()
+            .enumerate()
+            .map(|(idx, row)| {
+                let mut augmented = BitVector::from_indices(columns + 1, row.iter_ones());
+                augmented.set(columns, rhs.get(idx));
+                augmented
+            })
+            .collect();
+        Ok(Self { rows, columns })
+    }
+
+    /// One vector per unknown, giving the equations it appears in. Suits "which buttons toggle which lights" puzzles,
+    /// where each button is an unknown and each light an equation.
+    pub fn from_columns(columns: &[BitVector], rhs: &BitVector) -> Result<Self> {
+        if let Some(column) = columns.iter().find(|c| c.len() != rhs.len()) {
+            return Err(LinearError::dimension(format!(
+                "Column of length {} for {} equations",
+                column.len(),
+                rhs.len()
+            )));
+        }
+        let rows: Vec<BitVector> = (0..rhs.len())
+            .map(|row| {
+                BitVector::from_indices(
+                    columns.len(),
+                    columns
+                        .iter()
+                        .enumerate()
+                        .filter(|(_, column)| column.get(row))
+                        .map(|(idx, _)| idx),
+                )
+            })
+            .collect();
+        let mut system = Self::new(&rows, rhs)?;
+        system.columns = columns.len();
+        Ok(system)
+    }
+
+    /// Reduces the system to reduced row echelon form.
+    pub fn solve(&self) -> Gf2Solution {
+        let mut rows = self.rows.clone();
+        let mut pivots = Vec::new();
+        for column in 0..self.columns {
+            let rank = pivots.len();
+            if rank == rows.len() {
+                break;
+            }
+            let Some(pivot) = (rank..rows.len()).find(|&r| rows[r].get(column)) else {
+                continue;
+            };
+            rows.swap(rank, pivot);
+            let pivot_row = rows[rank].clone();
+            for (r, row) in rows.iter_mut().enumerate() {
+                if r != rank && row.get(column) {
+                    *row ^= &pivot_row;
+                }
+            }
+            pivots.push(column);
+        }
+
+        let rank = pivots.len();
+        let consistent = rows[rank..].iter().all(|row| !row.get(self.columns));
+        rows.truncate(rank);
+        let free = (0..self.columns).filter(|c| !pivots.contains(c)).collect();
+        Gf2Solution {
+            rank,
+            consistent,
+            pivots,
+            free,
+            reduced: rows,
+            columns: self.columns,
+        }
+    }
+}
+
+/// A GF(2) system in reduced row echelon form.
+#[derive(Debug, Clone)]
+pub struct Gf2Solution {
+    pub rank: usize,
+    /// Whether there is any solution at all.
+    pub consistent: bool,
+    /// The variable each non-zero row solves for.
+    pub pivots: Vec<usize>,
+    /// Variables that can take either value.
+    pub free: Vec<usize>,
+    reduced: Vec<BitVector>,
+    columns: usize,
+}
+
+impl Gf2Solution {
+    /// A solution with every free variable cleared.
+    pub fn particular(&self) -> Option<BitVector> {
+        self.consistent.then(|| {
+            let mut solution = BitVector::new(self.columns);
+            for (row, &pivot) in self.reduced.iter().zip(&self.pivots) {
+                solution.set(pivot, row.get(self.columns));
+            }
+            solution
+        })
+    }
+
+    /// A basis of the solutions to the homogeneous system, one vector per free variable.
+    /// Every solution is the particular solution XOR some subset of the basis.
+    pub fn null_space(&self) -> Vec<BitVector> {
+        self.free
+            .iter()
+            .map(|&f| {
+                let mut vector = BitVector::new(self.columns);
+                vector.set(f, true);
+                for (row, &pivot) in self.reduced.iter().zip(&self.pivots) {
+                    vector.set(pivot, row.get(f));
+                }
+                vector
+            })
+            .collect()
+    }
+
+    /// Every solution, 2^free of them, each differing from the last by one basis vector.
+    /// Panics with 64 or more free variables, as the count no longer fits in a u64.
+    pub fn solutions(&self) -> impl Iterator<Item = BitVector> {
+        let basis = self.null_space();
+        let count = match self.consistent {
+            true => 1u64
+                .checked_shl(basis.len() as u32)
+                .expect("too many free variables to enumerate"),
+            false => 0,
+        };
+        let mut current = self.particular();
+        (0..count).map_while(move |step| {
+            let solution = current.clone()?;
+            // Gray code order: the bit that flips between step and step + 1 picks the basis vector.
+            if let Some(next) = current.as_mut() {
+                if let Some(vector) = basis.get((step + 1).trailing_zeros() as usize) {
+                    *next ^= vector;
+                }
+            }
+            Some(solution)
+        })
+    }
+
+    /// The solution with the fewest set bits, or `None` if there is no solution.
+    /// Exhaustively searches all 2^free solutions, so only suits a handful of free variables,
+    /// and fails outright with 64 or more.
+    pub fn min_weight(&self) -> Result<Option<BitVector>> {
+        if self.free.len() >= WORD_BITS {
+            return Err(LinearError::search(format!(
+                "Too many free variables to search, got {}",
+                self.free.len()
+            )));
+        }
+        Ok(self.solutions().min_by_key(BitVector::count_ones))
+    }
+}
@@ -0,0 +1,320 @@
+/// Exact solving of small systems of linear equations.
+/// Responsibilities:
+/// - Fraction-exact Gaussian elimination to reduced row echelon form
+/// - Rank, consistency and free variable detection
+/// - Particular solutions and the null space
+/// - Enumerating non-negative integer solutions within bounds
+/// - The same elimination over GF(2) for XOR systems, with minimum weight solutions
+mod error;
+mod gf2;
+mod rational;
+pub use error::LinearError;
+use error::Result;
+pub use gf2::{BitVector, Gf2Solution, Gf2System};
+use num_traits::{CheckedDiv, CheckedMul, CheckedSub};
+pub use rational::Rational;
+
+/// A system of m equations in n unknowns, `coefficients · x = rhs`.
+#[derive(Debug, Clone)]
+pub struct LinearSystem {
+    /// Augmented rows, the last entry of each being the right hand side.
+    rows: Vec<Vec<Rational>>,
+    columns: usize,
+}
+
+impl LinearSystem {
+    /// One row of `coefficients` per equation, along with its right hand side in `rhs`.
+    pub fn new<T>(coefficients: &[Vec<T>], rhs: &[T]) -> Result<Self>
+    where
+        T: Into<Rational> + Copy,
+    {
+        if coefficients.is_empty() || coefficients.len() != rhs.len() {
+            return Err(LinearError::dimension(format!(
+                "{} rows of coefficients but {} right hand sides",
+                coefficients.len(),
+                rhs.len()
+            )));
+        }
+        let columns = coefficients[0].len();
+        if columns == 0 || coefficients.iter().any(|row| row.len() != columns) {
+            return Err(LinearError::dimension("Inconsistent row lengths"));
+        }
+        let rows = coefficients
+            .iter()
+            .zip(rhs)
+            .map(|(row, &rhs)| row.iter().chain([&rhs]).map(|&v| v.into()).collect())
+            .collect();
+        Ok(Self { rows, columns })
+    }
+
+    /// Number of equations.
+    pub fn equations(&self) -> usize {
+        self.rows.len()
+    }
+
+    /// Number of unknowns.
+    pub fn unknowns(&self) -> usize {
+        self.columns
+    }
+
+    /// Reduces the system to reduced row echelon form. Fails if a fraction outgrows i128 along the way.
+    pub fn solve(&self) -> Result<LinearSolution> {
+        let overflow = || LinearError::overflow("Elimination overflows an i128 fraction");
+        let mut rows = self.rows.clone();
+        let mut pivots = Vec::new();
+        for column in 0..self.columns {
+            let rank = pivots.len();
+            if rank == rows.len() {
+                break;
+            }
+            let Some(pivot) = (rank..rows.len()).find(|&r| !rows[r][column].is_zero()) else {
+                continue;
+            };
+            rows.swap(rank, pivot);
+            let scale = rows[rank][column];
+            for v in rows[rank].iter_mut() {
+                *v = v.checked_div(&scale).ok_or_else(overflow)?;
+            }
+
+            let pivot_row = rows[rank].clone();
+            for (r, row) in rows.iter_mut().enumerate() {
+                let factor = row[column];
+                if r == rank || factor.is_zero() {
+                    continue;
+                }
+                for (v, p) in row.iter_mut().zip(&pivot_row) {
+                    let product = factor.checked_mul(p).ok_or_else(overflow)?;
+                    *v = v.checked_sub(&product).ok_or_else(overflow)?;
+                }
+            }
+            pivots.push(column);
+        }
+
+        let rank = pivots.len();
+        // Every row past the rank has no coefficients left, so a non-zero right hand side means 0 = c.
+        let consistent = rows[rank..].iter().all(|row| row[self.columns].is_zero());
+        rows.truncate(rank);
+        let free = (0..self.columns).filter(|c| !pivots.contains(c)).collect();
+        Ok(LinearSolution {
+            rank,
+            consistent,
+            pivots,
+            free,
+            reduced: rows,
+            columns: self.columns,
+        })
+    }
+}
+
+/// A system in reduced row echelon form.
+#[derive(Debug, Clone)]
+pub struct LinearSolution {
+    pub rank: usize,
+    /// Whether there is any solution at all.
+    pub consistent: bool,
+    /// The variable each non-zero row solves for.
+    pub pivots: Vec<usize>,
+    /// Variables that can take any value.
+    pub free: Vec<usize>,
+    reduced: Vec<Vec<Rational>>,
+    columns: usize,
+}
+
+impl LinearSolution {
+    pub fn is_unique(&self) -> bool {
+        self.consistent && self.free.is_empty()
+    }
+
+    /// The only solution, if there is exactly one.
+    pub fn unique(&self) -> Option<Vec<Rational>> {
+        self.is_unique()
+            .then(|| self.reduced.iter().map(|row| row[self.columns]).collect())
+    }
+
+    /// A solution with every free variable set to zero.
+    pub fn particular(&self) -> Option<Vec<Rational>> {
+        self.consistent.then(|| {
+            let mut values = vec![Rational::ZERO; self.columns];
+            for (row, &pivot) in self.reduced.iter().zip(&self.pivots) {
+                values[pivot] = row[self.columns];
+            }
+            values
+        })
+    }
+
+    /// The full solution given a value for each free variable, in the order of `free`.
+    /// Fails if a value outgrows an i128 fraction.
+    pub fn evaluate(&self, free_values: &[Rational]) -> Result<Vec<Rational>> {
+        let overflow = || LinearError::overflow("Evaluation overflows an i128 fraction");
+        let mut values = vec![Rational::ZERO; self.columns];
+        for (&column, &value) in self.free.iter().zip(free_values) {
+            values[column] = value;
+        }
+        for (row, &pivot) in self.reduced.iter().zip(&self.pivots) {
+            let mut value = row[self.columns];
+            for &f in &self.free {
+                let product = row[f].checked_mul(&values[f]).ok_or_else(overflow)?;
+                value = value.checked_sub(&product).ok_or_else(overflow)?;
+            }
+            values[pivot] = value;
+        }
+        Ok(values)
+    }
+
+    /// A basis of the solutions to the homogeneous system, one vector per free variable.
+    pub fn null_space(&self) -> Vec<Vec<Rational>> {
+        self.free
+            .iter()
+            .map(|&f| {
+                let mut vector = vec![Rational::ZERO; self.columns];
+                vector[f] = Rational::ONE;
+                for (row, &pivot) in self.reduced.iter().zip(&self.pivots) {
+                    vector[pivot] = -row[f];
+                }
+                vector
+            })
+            .collect()
+    }
+
+    /// Every solution where each variable is an integer between 0 and its upper bound inclusive.
+    /// Tries every combination of free variables, so keep the number of free variables and their bounds small.
+    pub fn non_negative_integer_solutions<'a>(
+        &'a self,
+        upper_bounds: &'a [i64],
+    ) -> Result<impl Iterator<Item = Vec<i64>> + 'a> {
+        if upper_bounds.len() != self.columns {
+            return Err(LinearError::dimension(format!(
+                "{} upper bounds for {} unknowns",
+                upper_bounds.len(),
+                self.columns
+            )));
+        }
+        let mut next = (self.consistent && upper_bounds.iter().all(|&b| b >= 0))
+            .then(|| vec![0; self.free.len()]);
+
+        Ok(std::iter::from_fn(move || loop {
+            let current: Vec<i64> = next.clone()?;
+
+            // Advance like an odometer over the free variables.
+            next = None;
+            let mut candidate = current.clone();
+            for (idx, &f) in self.free.iter().enumerate() {
+                if candidate[idx] < upper_bounds[f] {
+                    candidate[idx] += 1;
+                    next = Some(candidate);
+                    break;
+                }
+                candidate[idx] = 0;
+            }
+
+            let free_values: Vec<Rational> = current.iter().map(|&v| v.into()).collect();
+            // A value too large for an i128 fraction is far outside any i64 bound, so it is no solution.
+            let Ok(values) = self.evaluate(&free_values) else {
+                continue;
+            };
+            let solution: Option<Vec<i64>> = values
+                .into_iter()
+                .zip(upper_bounds)
+                .map(|(v, &bound)| {
+                    v.to_integer()
+                        .and_then(|v| i64::try_from(v).ok())
+                        .filter(|v| (0..=bound).contains(v))
+                })
+                .collect();
+            if solution.is_some() {
+                return solution;
+            }
+        }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn system(coefficients: &[Vec<i64>], rhs: &[i64]) -> LinearSystem {
+        LinearSystem::new(coefficients, rhs).unwrap()
+    }
+
+    #[test]
+    fn unique_solution() {
+        // 2x + y = 5, x - y = 1
+        let solution = system(&[vec![2, 1], vec![1, -1]], &[5, 1]).solve().unwrap();
+        assert!(solution.is_unique());
+        assert_eq!(solution.rank, 2);
+        assert_eq!(
+            solution.unique(),
+            Some(vec![Rational::from(2i64), Rational::from(1i64)])
+        );
+    }
+
+    #[test]
+    fn rank_deficient_system() {
+        // x + y + z = 6 twice over, and 2x + 2y + 2z = 12
+        let solution = system(&[vec![1, 1, 1], vec![1, 1, 1], vec![2, 2, 2]], &[6, 6, 12])
+            .solve()
+            .unwrap();
+        assert!(solution.consistent);
+        assert_eq!(solution.rank, 1);
+        assert_eq!(solution.free, vec![1, 2]);
+        assert_eq!(solution.unique(), None);
+        let values = solution
+            .evaluate(&[Rational::from(1i64), Rational::from(2i64)])
+            .unwrap();
+        assert_eq!(values, [3i64, 1, 2].map(Rational::from));
+        for vector in solution.null_space() {
+            let sum = vector.into_iter().fold(Rational::ZERO, |acc, v| acc + v);
+            assert!(sum.is_zero());
+        }
+    }
+
+    #[test]
+    fn inconsistent_system() {
+        // x + y = 1 and x + y = 2
+        let solution = system(&[vec![1, 1], vec![1, 1]], &[1, 2]).solve().unwrap();
+        assert!(!solution.consistent);
+        assert_eq!(solution.particular(), None);
+        assert_eq!(
+            solution
+                .non_negative_integer_solutions(&[5, 5])
+                .unwrap()
+                .count(),
+            0
+        );
+    }
+
+    #[test]
+    fn bounded_non_negative_solutions() {
+        // x + y + z = 3, with z at most 1
+        let solution = system(&[vec![1, 1, 1]], &[3]).solve().unwrap();
+        let mut found: Vec<Vec<i64>> = solution
+            .non_negative_integer_solutions(&[3, 3, 1])
+            .unwrap()
+            .collect();
+        found.sort();
+        assert_eq!(
+            found,
+            vec![
+                vec![0, 2, 1],
+                vec![0, 3, 0],
+                vec![1, 1, 1],
+                vec![1, 2, 0],
+                vec![2, 0, 1],
+                vec![2, 1, 0],
+                vec![3, 0, 0],
+            ]
+        );
+        assert!(solution.non_negative_integer_solutions(&[3, 3]).is_err());
+    }
+
+    #[test]
+    fn elimination_overflow_is_an_error() {
+        let big = i64::MAX;
+        let result = system(
+            &[vec![big, 1, 0], vec![1, big, 1], vec![0, 1, big]],
+            &[1, 2, 3],
+        )
+        .solve();
+        assert!(matches!(result, Err(LinearError::OverflowError(_))));
+    }
+}
@@ -0,0 +1,193 @@
+/// Exact fractions for elimination without rounding.
+/// Responsibilities:
+/// - Keeping fractions in lowest terms with a positive denominator
+/// - Arithmetic, ordering and conversion to integers when exact
+use crate::helpers::number_theory::gcd;
+use num_traits::{CheckedAdd, CheckedDiv, CheckedMul, CheckedSub};
+use std::cmp::Ordering;
+use std::fmt;
+use std::ops::{Add, Div, Mul, Neg, Sub};
+
+/// An exact fraction, always stored in lowest terms with a positive denominator.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct Rational {
+    numerator: i128,
+    denominator: i128,
+}
+
+impl Rational {
+    pub const ZERO: Self = Self {
+        numerator: 0,
+        denominator: 1,
+    };
+    pub const ONE: Self = Self {
+        numerator: 1,
+        denominator: 1,
+    };
+
+    /// Panics if `denominator` is zero.
+    pub fn new(numerator: i128, denominator: i128) -> Self {
+        assert!(denominator != 0, "Rational with a zero denominator");
+        let divisor = gcd(numerator, denominator) * denominator.signum();
+        Self {
+            numerator: numerator / divisor,
+            denominator: denominator / divisor,
+        }
+    }
+
+    pub fn numerator(&self) -> i128 {
+        self.numerator
+    }
+
+    pub fn denominator(&self) -> i128 {
+        self.denominator
+    }
+
+    pub fn is_zero(&self) -> bool {
+        self.numerator == 0
+    }
+
+    pub fn is_integer(&self) -> bool {
+        self.denominator == 1
+    }
+
+    /// The value as an integer, if it is one.
+    pub fn to_integer(self) -> Option<i128> {
+        self.is_integer().then_some(self.numerator)
+    }
+
+    pub fn recip(self) -> Self {
+        Self::new(self.denominator, self.numerator)
+    }
+}
+
+impl From<i128> for Rational {
+    fn from(value: i128) -> Self {
+        Self::new(value, 1)
+    }
+}
+
+impl From<i64> for Rational {
+    fn from(value: i64) -> Self {
+        Self::new(value.into(), 1)
+    }
+}
+
+impl CheckedAdd for Rational {
+    fn checked_add(&self, other: &Self) -> Option<Self> {
+        let numerator = self
+            .numerator
+            .checked_mul(other.denominator)?
+            .checked_add(other.numerator.checked_mul(self.denominator)?)?;
+        Some(Self::new(
+            numerator,
+            self.denominator.checked_mul(other.denominator)?,
+        ))
+    }
+}
+
+impl CheckedSub for Rational {
+    fn checked_sub(&self, other: &Self) -> Option<Self> {
+        let negated = Self {
+            numerator: other.numerator.checked_neg()?,
+            denominator: other.denominator,
+        };
+        self.checked_add(&negated)
+    }
+}
+
+impl CheckedMul for Rational {
+    fn checked_mul(&self, other: &Self) -> Option<Self> {
+        Some(Self::new(
+            self.numerator.checked_mul(other.numerator)?,
+            self.denominator.checked_mul(other.denominator)?,
+        ))
+    }
+}
+
+impl CheckedDiv for Rational {
+    /// `None` if `other` is zero or the result overflows.
+    fn checked_div(&self, other: &Self) -> Option<Self> {
+        if other.is_zero() {
+            return None;
+        }
+        Some(Self::new(
+            self.numerator.checked_mul(other.denominator)?,
+            self.denominator.checked_mul(other.numerator)?,
+        ))
+    }
+}
+
+impl Add for Rational {
+    type Output = Self;
+
+    /// Panics on overflow; use `checked_add` to handle it.
+    fn add(self, other: Self) -> Self::Output {
+        self.checked_add(&other)
+            .expect("Rational addition overflowed")
+    }
+}
+
+impl Sub for Rational {
+    type Output = Self;
+
+    /// Panics on overflow; use `checked_sub` to handle it.
+    fn sub(self, other: Self) -> Self::Output {
+        self.checked_sub(&other)
+            .expect("Rational subtraction overflowed")
+    }
+}
+
+impl Mul for Rational {
+    type Output = Self;
+
+    /// Panics on overflow; use `checked_mul` to handle it.
+    fn mul(self, other: Self) -> Self::Output {
+        self.checked_mul(&other)
+            .expect("Rational multiplication overflowed")
+    }
+}
+
+impl Div for Rational {
+    type Output = Self;
+
+    /// Panics if `other` is zero or on overflow; use `checked_div` to handle either.
+    fn div(self, other: Self) -> Self::Output {
+        assert!(!other.is_zero(), "Rational division by zero");
+        self.checked_div(&other)
+            .expect("Rational division overflowed")
+    }
+}
+
+impl Neg for Rational {
+    type Output = Self;
+
+    fn neg(self) -> Self::Output {
+        Self {
+            numerator: -self.numerator,
+            denominator: self.denominator,
+        }
+    }
+}
+
+impl PartialOrd for Rational {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Rational {
+    fn cmp(&self, other: &Self) -> Ordering {
+        (self.numerator * other.denominator).cmp(&(other.numerator * self.denominator))
+    }
+}
+
+impl fmt::Display for Rational {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.is_integer() {
+            write!(f, "{}", self.numerator)
+        } else {
+            write!(f, "{}/{}", self.numerator, self.denominator)
+        }
+    }
+}
@@ -1,4 +1,13 @@
 /// Helper methods for commonly used functionality. Is it useful? Probably not. Am I learning a lot? Absolutely.
+pub mod cycle;
 pub mod direction;
+pub mod graph;
 pub mod grid;
+pub mod line;
+pub mod linear;
+pub mod number_theory;
 pub mod point;
+pub mod polygon;
+pub mod range_set;
+pub mod spatial;
+pub mod union_find;
@@ -0,0 +1,220 @@
+/// Integer number theory over any primitive integer type.
+/// Responsibilities:
+/// - gcd and lcm, pairwise and folded over iterators
+/// - Extended gcd and modular inverses
+/// - Overflow-free modular multiplication and exponentiation
+/// - Chinese remainder theorem over any number of congruences, coprime or not
+/// - A prime sieve
+/// - Modular integers with a compile-time or runtime modulus
+use num_traits::{PrimInt, Signed};
+
+mod mod_int;
+pub use mod_int::{DynModInt, ModInt};
+
+/// Greatest common divisor, always non-negative.
+/// Panics if the result does not fit, which only happens when one value is `T::MIN` and the other is 0 or `T::MIN`.
+pub fn gcd<T: PrimInt>(a: T, b: T) -> T {
+    if T::min_value() == T::zero() {
+        let (mut a, mut b) = (a, b);
+        while b != T::zero() {
+            (a, b) = (b, a % b);
+        }
+        return a;
+    }
+    // Signed values are made non-positive instead, as the negatives reach further than the positives.
+    let non_positive = |value: T| {
+        if value > T::zero() {
+            T::zero() - value
+        } else {
+            value
+        }
+    };
+    let (mut a, mut b) = (non_positive(a), non_positive(b));
+    while b != T::zero() {
+        // T::MIN % -1 overflows even though the remainder is 0.
+        let remainder = if b == T::zero() - T::one() {
+            T::zero()
+        } else {
+            a % b
+        };
+        (a, b) = (b, remainder);
+    }
+    checked_abs(a).expect("gcd should fit in the integer type")
+}
+
+/// Least common multiple, always non-negative. `None` if it overflows.
+pub fn lcm<T: PrimInt>(a: T, b: T) -> Option<T> {
+    if a == T::zero() || b == T::zero() {
+        return Some(T::zero());
+    }
+    // |T::MIN| does not fit, and neither does any multiple of it.
+    let (abs_a, abs_b) = (checked_abs(a)?, checked_abs(b)?);
+    (abs_a / gcd(a, b)).checked_mul(&abs_b)
+}
+
+/// gcd of every value, zero if there are none.
+pub fn gcd_all<T: PrimInt>(values: impl IntoIterator<Item = T>) -> T {
+    values.into_iter().fold(T::zero(), gcd)
+}
+
+/// lcm of every value, one if there are none. `None` if it overflows.
+pub fn lcm_all<T: PrimInt>(values: impl IntoIterator<Item = T>) -> Option<T> {
+    values
+        .into_iter()
+        .try_fold(T::one(), |acc, value| lcm(acc, value))
+}
+
+/// Returns (g, x, y) such that a·x + b·y = g = gcd(a, b).
+pub fn extended_gcd<T: PrimInt + Signed>(a: T, b: T) -> (T, T, T) {
+    let (mut old_r, mut r) = (a, b);
+    let (mut old_x, mut x) = (T::one(), T::zero());
+    let (mut old_y, mut y) = (T::zero(), T::one());
+    while r != T::zero() {
+        let quotient = old_r / r;
+        (old_r, r) = (r, old_r - quotient * r);
+        (old_x, x) = (x, old_x - quotient * x);
+        (old_y, y) = (y, old_y - quotient * y);
+    }
+    if old_r < T::zero() {
+        (-old_r, -old_x, -old_y)
+    } else {
+        (old_r, old_x, old_y)
+    }
+}
+
+/// `value` reduced into `0..modulus`, for a positive modulus. Panics if `modulus` is zero.
+pub fn modulo<T: PrimInt>(value: T, modulus: T) -> T {
+    let rem = value % modulus;
+    if rem < T::zero() {
+        rem + modulus
+    } else {
+        rem
+    }
+}
+
+/// The x in `0..modulus` with a·x ≡ 1, if a and the modulus are coprime. `None` for a modulus that is not positive.
+pub fn mod_inverse<T: PrimInt + Signed>(a: T, modulus: T) -> Option<T> {
+    if modulus <= T::zero() {
+        return None;
+    }
+    let (g, x, _) = extended_gcd(modulo(a, modulus), modulus);
+    (g == T::one()).then(|| modulo(x, modulus))
+}
+
+/// a + b mod m for a and b already in `0..m`, without overflowing.
+fn add_mod<T: PrimInt>(a: T, b: T, modulus: T) -> T {
+    if a >= modulus - b {
+        a - (modulus - b)
+    } else {
+        a + b
+    }
+}
+
+/// a·b mod m, falling back to double-and-add when the product would overflow.
+pub fn mul_mod<T: PrimInt>(a: T, b: T, modulus: T) -> T {
+    let (mut a, mut b) = (modulo(a, modulus), modulo(b, modulus));
+    if let Some(product) = a.checked_mul(&b) {
+        return product % modulus;
+    }
+    let mut result = T::zero();
+    while b > T::zero() {
+        if b & T::one() == T::one() {
+            result = add_mod(result, a, modulus);
+        }
+        a = add_mod(a, a, modulus);
+        b = b >> 1;
+    }
+    result
+}
+
+/// base^exponent mod m by repeated squaring.
+pub fn mod_pow<T: PrimInt>(base: T, mut exponent: u64, modulus: T) -> T {
+    let mut base = modulo(base, modulus);
+    let mut result = modulo(T::one(), modulus);
+    while exponent > 0 {
+        if exponent & 1 == 1 {
+            result = mul_mod(result, base, modulus);
+        }
+        base = mul_mod(base, base, modulus);
+        exponent >>= 1;
+    }
+    result
+}
+
+/// Solves x ≡ residue (mod modulus) for every (residue, modulus) pair.
+/// Returns (x, lcm of the moduli) with x in `0..lcm`, or `None` if the congruences contradict each other,
+/// the combined modulus overflows, or a modulus is not positive. Moduli need not be coprime.
+pub fn crt<T: PrimInt + Signed>(congruences: impl IntoIterator<Item = (T, T)>) -> Option<(T, T)> {
+    congruences
+        .into_iter()
+        .try_fold((T::zero(), T::one()), |(r1, m1), (r2, m2)| {
+            if m2 <= T::zero() {
+                return None;
+            }
+            let r2 = modulo(r2, m2);
+            let g = gcd(m1, m2);
+            let difference = r2 - r1;
+            if difference % g != T::zero() {
+                return None;
+            }
+            // Find k with r1 + m1·k ≡ r2 (mod m2), working modulo m2 / g.
+            let step = m2 / g;
+            let k = mul_mod(difference / g, mod_inverse(m1 / g, step)?, step);
+            let combined = lcm(m1, m2)?;
+            Some((add_mod(r1, mul_mod(m1, k, combined), combined), combined))
+        })
+}
+
+/// |value|, or `None` for `T::MIN` of a signed type.
+fn checked_abs<T: PrimInt>(value: T) -> Option<T> {
+    if value < T::zero() {
+        T::zero().checked_sub(&value)
+    } else {
+        Some(value)
+    }
+}
+
+/// Primality of every number up to a limit, by the sieve of Eratosthenes.
+#[derive(Debug, Clone)]
+pub struct Sieve {
+    is_prime: Vec<bool>,
+}
+
+impl Sieve {
+    /// Sieves every number up to and including `limit`.
+    pub fn new(limit: usize) -> Self {
+        let mut is_prime = vec![true; limit + 1];
+        is_prime[0] = false;
+        if limit >= 1 {
+            is_prime[1] = false;
+        }
+        let mut n = 2;
+        while n * n <= limit {
+            if is_prime[n] {
+                (n * n..=limit)
+                    .step_by(n)
+                    .for_each(|multiple| is_prime[multiple] = false);
+            }
+            n += 1;
+        }
+        Self { is_prime }
+    }
+
+    pub fn limit(&self) -> usize {
+        self.is_prime.len() - 1
+    }
+
+    /// `None` if `n` is past the limit.
+    pub fn is_prime(&self, n: usize) -> Option<bool> {
+        self.is_prime.get(n).copied()
+    }
+
+    /// Every prime up to the limit, in increasing order.
+    pub fn primes(&self) -> impl Iterator<Item = usize> + '_ {
+        self.is_prime
+            .iter()
+            .enumerate()
+            .filter(|(_, &prime)| prime)
+            .map(|(n, _)| n)
+    }
+}
@@ -0,0 +1,303 @@
+/// Integers modulo a fixed modulus.
+/// Responsibilities:
+/// - ModInt with a compile-time modulus and DynModInt with a runtime one
+/// - Arithmetic, powers and inverses that stay reduced
+/// - Points on a torus, each coordinate wrapping with its own modulus
+use super::{mod_inverse, modulo};
+use crate::helpers::grid::GridPoint;
+use crate::helpers::point::Point;
+use num_traits::{One, PrimInt, Zero};
+use std::fmt;
+use std::ops::{Add, AddAssign, Mul, MulAssign, Neg, Sub, SubAssign};
+
+fn reduce(value: i128, modulus: u64) -> u64 {
+    modulo(value, i128::from(modulus)) as u64
+}
+
+/// Reduces any primitive integer, including u128 values beyond the range of i128.
+fn reduce_int<I: PrimInt>(value: I, modulus: u64) -> u64 {
+    match value.to_i128() {
+        Some(value) => reduce(value, modulus),
+        None => {
+            let value = value
+                .to_u128()
+                .expect("primitive integers fit in i128 or u128");
+            (value % u128::from(modulus)) as u64
+        }
+    }
+}
+
+fn mul(a: u64, b: u64, modulus: u64) -> u64 {
+    (u128::from(a) * u128::from(b) % u128::from(modulus)) as u64
+}
+
+fn pow(mut base: u64, mut exponent: u64, modulus: u64) -> u64 {
+    let mut result = 1 % modulus;
+    while exponent > 0 {
+        if exponent & 1 == 1 {
+            result = mul(result, base, modulus);
+        }
+        base = mul(base, base, modulus);
+        exponent >>= 1;
+    }
+    result
+}
+
+fn inverse(value: u64, modulus: u64) -> Option<u64> {
+    mod_inverse(i128::from(value), i128::from(modulus)).map(|v| v as u64)
+}
+
+/// An integer modulo `M`, fixed at compile time. Always held in `0..M`, so a zero `M` fails to compile.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct ModInt<const M: u64> {
+    value: u64,
+}
+
+impl<const M: u64> ModInt<M> {
+    pub const MODULUS: u64 = M;
+
+    /// Reduces any integer into `0..M`, negative values wrapping around.
+    pub fn new(value: impl PrimInt) -> Self {
+        const { assert!(M > 0, "ModInt with a zero modulus") };
+        Self {
+            value: reduce_int(value, M),
+        }
+    }
+
+    pub fn value(self) -> u64 {
+        self.value
+    }
+
+    pub fn pow(self, exponent: u64) -> Self {
+        Self {
+            value: pow(self.value, exponent, M),
+        }
+    }
+
+    /// The multiplicative inverse, if the value is coprime to `M`.
+    pub fn inverse(self) -> Option<Self> {
+        inverse(self.value, M).map(|value| Self { value })
+    }
+}
+
+impl<const M: u64> Add for ModInt<M> {
+    type Output = Self;
+
+    fn add(self, other: Self) -> Self::Output {
+        Self::new(i128::from(self.value) + i128::from(other.value))
+    }
+}
+
+impl<const M: u64> Sub for ModInt<M> {
+    type Output = Self;
+
+    fn sub(self, other: Self) -> Self::Output {
+        Self::new(i128::from(self.value) - i128::from(other.value))
+    }
+}
+
+impl<const M: u64> Mul for ModInt<M> {
+    type Output = Self;
+
+    fn mul(self, other: Self) -> Self::Output {
+        Self {
+            value: mul(self.value, other.value, M),
+        }
+    }
+}
+
+impl<const M: u64> Neg for ModInt<M> {
+    type Output = Self;
+
+    fn neg(self) -> Self::Output {
+        Self::new(-i128::from(self.value))
+    }
+}
+
+impl<const M: u64> AddAssign for ModInt<M> {
+    fn add_assign(&mut self, other: Self) {
+        *self = *self + other;
+    }
+}
+
+impl<const M: u64> SubAssign for ModInt<M> {
+    fn sub_assign(&mut self, other: Self) {
+        *self = *self - other;
+    }
+}
+
+impl<const M: u64> MulAssign for ModInt<M> {
+    fn mul_assign(&mut self, other: Self) {
+        *self = *self * other;
+    }
+}
+
+impl<const M: u64> Zero for ModInt<M> {
+    fn zero() -> Self {
+        Self::new(0)
+    }
+
+    fn is_zero(&self) -> bool {
+        self.value == 0
+    }
+}
+
+impl<const M: u64> Default for ModInt<M> {
+    fn default() -> Self {
+        Self::zero()
+    }
+}
+
+impl<const M: u64> One for ModInt<M> {
+    fn one() -> Self {
+        Self::new(1)
+    }
+}
+
+impl<const M: u64> fmt::Display for ModInt<M> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.value)
+    }
+}
+
+/// An integer modulo a value chosen at runtime. Always held in `0..modulus`.
+/// Arithmetic between values with different moduli panics.
+/// Each coordinate of a `Point<DynModInt>` can have its own modulus, giving positions on a torus.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct DynModInt {
+    value: u64,
+    modulus: u64,
+}
+
+impl DynModInt {
+    /// Reduces any integer into `0..modulus`, negative values wrapping around.
+    /// Panics if `modulus` is zero.
+    pub fn new(value: impl PrimInt, modulus: u64) -> Self {
+        assert!(modulus > 0, "DynModInt with a zero modulus");
+        Self {
+            value: reduce_int(value, modulus),
+            modulus,
+        }
+    }
+
+    pub fn value(self) -> u64 {
+        self.value
+    }
+
+    pub fn modulus(self) -> u64 {
+        self.modulus
+    }
+
+    /// Another value with the same modulus.
+    pub fn with_value(self, value: impl PrimInt) -> Self {
+        Self::new(value, self.modulus)
+    }
+
+    pub fn pow(self, exponent: u64) -> Self {
+        Self {
+            value: pow(self.value, exponent, self.modulus),
+            modulus: self.modulus,
+        }
+    }
+
+    /// The multiplicative inverse, if the value is coprime to the modulus.
+    pub fn inverse(self) -> Option<Self> {
+        inverse(self.value, self.modulus).map(|value| Self {
+            value,
+            modulus: self.modulus,
+        })
+    }
+
+    fn check_modulus(self, other: Self) {
+        assert_eq!(
+            self.modulus, other.modulus,
+            "arithmetic between different moduli"
+        );
+    }
+}
+
+impl Add for DynModInt {
+    type Output = Self;
+
+    fn add(self, other: Self) -> Self::Output {
+        self.check_modulus(other);
+        self.with_value(i128::from(self.value) + i128::from(other.value))
+    }
+}
+
+impl Sub for DynModInt {
+    type Output = Self;
+
+    fn sub(self, other: Self) -> Self::Output {
+        self.check_modulus(other);
+        self.with_value(i128::from(self.value) - i128::from(other.value))
+    }
+}
+
+impl Mul for DynModInt {
+    type Output = Self;
+
+    fn mul(self, other: Self) -> Self::Output {
+        self.check_modulus(other);
+        Self {
+            value: mul(self.value, other.value, self.modulus),
+            modulus: self.modulus,
+        }
+    }
+}
+
+impl Neg for DynModInt {
+    type Output = Self;
+
+    fn neg(self) -> Self::Output {
+        self.with_value(-i128::from(self.value))
+    }
+}
+
+impl AddAssign for DynModInt {
+    fn add_assign(&mut self, other: Self) {
+        *self = *self + other;
+    }
+}
+
+impl SubAssign for DynModInt {
+    fn sub_assign(&mut self, other: Self) {
+        *self = *self - other;
+    }
+}
+
+impl MulAssign for DynModInt {
+    fn mul_assign(&mut self, other: Self) {
+        *self = *self * other;
+    }
+}
+
+impl fmt::Display for DynModInt {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} (mod {})", self.value, self.modulus)
+    }
+}
+
+impl Point<DynModInt> {
+    /// A position on a torus with the given (rows, cols) bounds, each coordinate wrapping independently.
+    pub fn toroidal<P: PrimInt>(point: Point<P>, bounds: GridPoint) -> Self {
+        Point::new(
+            DynModInt::new(point.x, bounds.x as u64),
+            DynModInt::new(point.y, bounds.y as u64),
+        )
+    }
+
+    /// The position after moving by `velocity` for `steps` steps.
+    pub fn moved_by<P: Into<i128>>(self, velocity: Point<P>, steps: i64) -> Self {
+        let steps = i128::from(steps);
+        Point::new(
+            self.x + self.x.with_value(velocity.x.into() * steps),
+            self.y + self.y.with_value(velocity.y.into() * steps),
+        )
+    }
+
+    /// The position as a grid point, always within the bounds.
+    pub fn as_grid_point(&self) -> GridPoint {
+        GridPoint::new(self.x.value() as usize, self.y.value() as usize)
+    }
+}
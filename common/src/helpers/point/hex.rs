@@ -0,0 +1,149 @@
+/// A hexagonal cell in axial coordinates.
+/// Responsibilities:
+/// - Axial and cube coordinate access and conversion to and from Point
+/// - Arithmetic and stepping in HexDirections
+/// - Hex distance
+/// - Rings and spirals of cells around a centre
+/// - Rotation about the origin
+use super::Point;
+use crate::helpers::direction::{DirectionBehaviour, HexDirections};
+use num_traits::{PrimInt, Signed};
+use std::fmt;
+use std::ops::{Add, AddAssign, Mul, Neg, Sub, SubAssign};
+
+/// A hex at axial column `q` and row `r`. The implied third cube coordinate is `s = -q - r`.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq, Hash, Ord, PartialOrd)]
+pub struct Hex<P> {
+    pub q: P,
+    pub r: P,
+}
+
+impl<P> Hex<P> {
+    pub fn new(q: P, r: P) -> Self {
+        Self { q, r }
+    }
+}
+
+impl<P: PrimInt + Signed> Hex<P> {
+    pub fn origin() -> Self {
+        Self::new(P::zero(), P::zero())
+    }
+
+    pub fn s(&self) -> P {
+        -self.q - self.r
+    }
+
+    /// The (q, r, s) cube coordinates, which always sum to zero.
+    pub fn cube(&self) -> (P, P, P) {
+        (self.q, self.r, self.s())
+    }
+
+    /// The hex `steps` away in `direction`.
+    pub fn step(self, direction: HexDirections, steps: P) -> Self {
+        self + Self::from(direction.as_delta::<P>()).scale(steps)
+    }
+
+    pub fn neighbour(self, direction: HexDirections) -> Self {
+        self.step(direction, P::one())
+    }
+
+    /// The six neighbours, clockwise from NorthEast.
+    pub fn neighbours(self) -> impl Iterator<Item = (HexDirections, Self)> {
+        HexDirections::iter_all().map(move |&direction| (direction, self.neighbour(direction)))
+    }
+
+    /// Fewest steps from the origin.
+    pub fn length(&self) -> P {
+        let (q, r, s) = self.cube();
+        q.abs().max(r.abs()).max(s.abs())
+    }
+
+    /// Fewest steps between two hexes.
+    pub fn distance(&self, other: &Self) -> P {
+        (*self - *other).length()
+    }
+
+    /// The hexes exactly `radius` steps away, clockwise from the one furthest NorthEast.
+    /// A radius of zero gives just the centre.
+    pub fn ring(self, radius: P) -> impl Iterator<Item = Self> {
+        let start = self.step(HexDirections::NorthEast, radius);
+        let length = radius.to_usize().unwrap_or(0);
+        let sides = if length == 0 { 1 } else { HexDirections::COUNT };
+        (0..sides)
+            .flat_map(move |side| (0..length.max(1)).map(move |step| (side, step)))
+            .scan(start, move |current, (side, _)| {
+                let hex = *current;
+                *current = current.neighbour(HexDirections::ALL[(side + 2) % HexDirections::COUNT]);
+                Some(hex)
+            })
+    }
+
+    /// Every hex within `radius` steps, ring by ring outwards from the centre.
+    pub fn spiral(self, radius: P) -> impl Iterator<Item = Self> {
+        let radius = radius.to_usize().unwrap_or(0);
+        (0..=radius).flat_map(move |ring| self.ring(P::from(ring).expect("radius fits in P")))
+    }
+
+    /// Rotated about the origin by 60° clockwise for each of `turns`, anticlockwise when negative.
+    pub fn rotate(self, turns: i32) -> Self {
+        (0..turns.rem_euclid(6)).fold(self, |hex, _| {
+            let (_, r, s) = hex.cube();
+            Self::new(-r, -s)
+        })
+    }
+
+    /// As a Point with x the row `r` and y the column `q`, matching `HexDirections::as_delta`.
+    pub fn as_point(&self) -> Point<P> {
+        Point::new(self.r, self.q)
+    }
+}
+
+impl<P: Mul<Output = P> + Copy> Hex<P> {
+    pub fn scale(&self, factor: P) -> Self {
+        Self::new(self.q * factor, self.r * factor)
+    }
+}
+
+impl<P> From<Point<P>> for Hex<P> {
+    /// Reads x as the row `r` and y as the column `q`.
+    fn from(point: Point<P>) -> Self {
+        Self::new(point.y, point.x)
+    }
+}
+
+impl<P: Add<Output = P>> Add for Hex<P> {
+    type Output = Self;
+    fn add(self, other: Self) -> Self::Output {
+        Self::new(self.q + other.q, self.r + other.r)
+    }
+}
+impl<P: AddAssign> AddAssign for Hex<P> {
+    fn add_assign(&mut self, other: Self) {
+        self.q += other.q;
+        self.r += other.r;
+    }
+}
+impl<P: Sub<Output = P>> Sub for Hex<P> {
+    type Output = Self;
+    fn sub(self, other: Self) -> Self::Output {
+        Self::new(self.q - other.q, self.r - other.r)
+    }
+}
+impl<P: SubAssign> SubAssign for Hex<P> {
+    fn sub_assign(&mut self, other: Self) {
+        self.q -= other.q;
+        self.r -= other.r;
+    }
+}
+impl<P: Neg<Output = P>> Neg for Hex<P> {
+    type Output = Self;
+    fn neg(self) -> Self::Output {
+        Self::new(-self.q, -self.r)
+    }
+}
+
+impl<P: fmt::Display> fmt::Display for Hex<P> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "({}, {})", self.q, self.r)
+    }
+}
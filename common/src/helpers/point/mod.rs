@@ -9,7 +9,11 @@ use std::fmt::{self, Debug, Display};
 use std::ops::{Add, AddAssign, Div, Index, IndexMut, Mul, Neg, Rem, Sub, SubAssign};
 
 mod error;
+mod hex;
+mod point3;
 use error::{PointError, Result};
+pub use hex::Hex;
+pub use point3::{Connectivity3, Point3};
 
 use super::direction::DirectionBehaviour;
 
@@ -262,6 +266,22 @@ where
     }
 }
 
+impl<P> Point<P>
+where
+    P: ToPrimitive + Copy,
+{
+    /// Wraps the point into the given bounds (exclusive) as if the space were a torus.
+    /// Gives `None` when either bound is zero, as nothing lies within it.
+    pub fn wrapped_as<U>(&self, bounds: &Point<U>) -> Option<Point<U>>
+    where
+        U: ToPrimitive + NumCast + Copy,
+    {
+        let wrap =
+            |value: P, bound: U| U::from(value.to_i128()?.checked_rem_euclid(bound.to_i128()?)?);
+        Some(Point::new(wrap(self.x, bounds.x)?, wrap(self.y, bounds.y)?))
+    }
+}
+
 impl<P> Point<P>
 where
     P: Copy + Zero + PartialOrd,
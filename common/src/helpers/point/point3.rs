@@ -0,0 +1,283 @@
+/// A 3D point with x, y and z coordinates.
+/// Responsibilities:
+/// - Arithmetic, checked and scalar operations
+/// - Casting between coordinate types
+/// - Manhattan, Chebyshev and Euclidean distances
+/// - Face, edge and vertex neighbourhoods (6, 18 and 26 neighbours)
+use super::error::{PointError, Result};
+use num_traits::{CheckedAdd, CheckedSub, NumCast, PrimInt, ToPrimitive, Zero};
+use std::fmt;
+use std::ops::{Add, AddAssign, Index, IndexMut, Mul, Neg, Sub, SubAssign};
+
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq, Hash, Ord, PartialOrd)]
+pub struct Point3<P> {
+    pub x: P,
+    pub y: P,
+    pub z: P,
+}
+
+/// Which cells around a 3D cell count as its neighbours.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Connectivity3 {
+    /// The 6 cells sharing a face.
+    Face,
+    /// The 18 cells sharing a face or an edge.
+    Edge,
+    /// The 26 cells sharing a face, an edge or a corner.
+    Vertex,
+}
+
+impl Connectivity3 {
+    /// Offsets to each neighbour, faces first.
+    pub fn offsets(self) -> Vec<Point3<i32>> {
+        let mut offsets: Vec<Point3<i32>> = (-1..=1)
+            .flat_map(|x| (-1..=1).flat_map(move |y| (-1..=1).map(move |z| Point3::new(x, y, z))))
+            .filter(|offset| match offset.manhattan_distance(&Point3::zero()) {
+                0 => false,
+                1 => true,
+                2 => self != Self::Face,
+                _ => self == Self::Vertex,
+            })
+            .collect();
+        offsets.sort_by_key(|offset| offset.manhattan_distance(&Point3::zero()));
+        offsets
+    }
+
+    pub fn count(self) -> usize {
+        match self {
+            Self::Face => 6,
+            Self::Edge => 18,
+            Self::Vertex => 26,
+        }
+    }
+}
+
+impl<P> Point3<P> {
+    pub fn new(x: P, y: P, z: P) -> Self {
+        Self { x, y, z }
+    }
+
+    /// Maps every coordinate using the provided function
+    pub fn map<U, F: FnMut(P) -> U>(self, mut f: F) -> Point3<U> {
+        Point3::new(f(self.x), f(self.y), f(self.z))
+    }
+
+    pub fn to_array(self) -> [P; 3] {
+        [self.x, self.y, self.z]
+    }
+}
+
+impl<P: Zero> Point3<P> {
+    pub fn zero() -> Self {
+        Self::new(P::zero(), P::zero(), P::zero())
+    }
+
+    pub fn is_origin(&self) -> bool
+    where
+        P: PartialEq,
+    {
+        self.x.is_zero() && self.y.is_zero() && self.z.is_zero()
+    }
+}
+
+impl<P> Index<usize> for Point3<P> {
+    type Output = P;
+    /// Access x, y and z with indices 0, 1 and 2.
+    fn index(&self, index: usize) -> &Self::Output {
+        match index {
+            0 => &self.x,
+            1 => &self.y,
+            2 => &self.z,
+            _ => panic!("Point3 index out of bounds"),
+        }
+    }
+}
+
+impl<P> IndexMut<usize> for Point3<P> {
+    /// Access x, y and z with indices 0, 1 and 2.
+    fn index_mut(&mut self, index: usize) -> &mut Self::Output {
+        match index {
+            0 => &mut self.x,
+            1 => &mut self.y,
+            2 => &mut self.z,
+            _ => panic!("Point3 index out of bounds"),
+        }
+    }
+}
+
+// Numeric operations
+impl<P: Add<Output = P>> Add for Point3<P> {
+    type Output = Self;
+    fn add(self, other: Self) -> Self::Output {
+        Self::new(self.x + other.x, self.y + other.y, self.z + other.z)
+    }
+}
+impl<P: AddAssign> AddAssign for Point3<P> {
+    fn add_assign(&mut self, other: Self) {
+        self.x += other.x;
+        self.y += other.y;
+        self.z += other.z;
+    }
+}
+impl<P: Sub<Output = P>> Sub for Point3<P> {
+    type Output = Self;
+    fn sub(self, other: Self) -> Self::Output {
+        Self::new(self.x - other.x, self.y - other.y, self.z - other.z)
+    }
+}
+impl<P: SubAssign> SubAssign for Point3<P> {
+    fn sub_assign(&mut self, other: Self) {
+        self.x -= other.x;
+        self.y -= other.y;
+        self.z -= other.z;
+    }
+}
+impl<P: Neg<Output = P>> Neg for Point3<P> {
+    type Output = Self;
+    fn neg(self) -> Self::Output {
+        Self::new(-self.x, -self.y, -self.z)
+    }
+}
+impl<P: CheckedAdd> Point3<P> {
+    /// Add two points together and return the result if the operation is successful.
+    pub fn checked_add(self, other: Self) -> Option<Self> {
+        Some(Self::new(
+            self.x.checked_add(&other.x)?,
+            self.y.checked_add(&other.y)?,
+            self.z.checked_add(&other.z)?,
+        ))
+    }
+}
+impl<P: CheckedSub> Point3<P> {
+    /// Subtract one point from another and return the result if the operation is successful.
+    pub fn checked_sub(self, other: Self) -> Option<Self> {
+        Some(Self::new(
+            self.x.checked_sub(&other.x)?,
+            self.y.checked_sub(&other.y)?,
+            self.z.checked_sub(&other.z)?,
+        ))
+    }
+}
+impl<P: Mul<Output = P> + Copy> Point3<P> {
+    pub fn scale(&self, factor: P) -> Self {
+        Self::new(self.x * factor, self.y * factor, self.z * factor)
+    }
+}
+
+impl<P> Point3<P>
+where
+    P: Copy + Zero + PartialOrd,
+{
+    /// Whether every coordinate is within `0..bounds`.
+    pub fn check_bounds(&self, bounds: &Self) -> bool {
+        self.x >= P::zero()
+            && self.y >= P::zero()
+            && self.z >= P::zero()
+            && self.x < bounds.x
+            && self.y < bounds.y
+            && self.z < bounds.z
+    }
+}
+
+// Distance calculations
+impl<P: PrimInt> Point3<P> {
+    fn abs_differences(&self, other: &Self) -> [P; 3] {
+        let diff = |a: P, b: P| if a > b { a - b } else { b - a };
+        [
+            diff(self.x, other.x),
+            diff(self.y, other.y),
+            diff(self.z, other.z),
+        ]
+    }
+
+    pub fn manhattan_distance(&self, other: &Self) -> P {
+        let [x, y, z] = self.abs_differences(other);
+        x + y + z
+    }
+
+    pub fn chebyshev_distance(&self, other: &Self) -> P {
+        let [x, y, z] = self.abs_differences(other);
+        x.max(y).max(z)
+    }
+
+    /// Neighbours over the given connectivity, skipping any that do not fit in `P`.
+    pub fn neighbours(self, connectivity: Connectivity3) -> impl Iterator<Item = Self> {
+        connectivity
+            .offsets()
+            .into_iter()
+            .filter_map(move |offset| {
+                let shift = |value: P, by: i32| <P as NumCast>::from(value.to_i64()? + by as i64);
+                Some(Self::new(
+                    shift(self.x, offset.x)?,
+                    shift(self.y, offset.y)?,
+                    shift(self.z, offset.z)?,
+                ))
+            })
+    }
+}
+
+impl<P> Point3<P>
+where
+    P: Copy + Sub<Output = P> + Mul<Output = P> + Add<Output = P>,
+{
+    /// Squared straight line distance, exact for integer coordinates. Unsigned coordinates can underflow.
+    pub fn squared_distance(&self, other: &Self) -> P {
+        let (x, y, z) = (self.x - other.x, self.y - other.y, self.z - other.z);
+        x * x + y * y + z * z
+    }
+}
+
+impl<P: ToPrimitive + Copy> Point3<P> {
+    /// Straight line distance. Panics if a coordinate cannot be represented as an f64.
+    pub fn euclidean_distance(&self, other: &Self) -> f64 {
+        let as_f64 = |p: &Self| p.map(|v| v.to_f64().expect("coordinate should fit in an f64"));
+        as_f64(self).squared_distance(&as_f64(other)).sqrt()
+    }
+}
+
+// Conversions
+impl<P> From<(P, P, P)> for Point3<P> {
+    fn from((x, y, z): (P, P, P)) -> Self {
+        Self::new(x, y, z)
+    }
+}
+impl<P> From<Point3<P>> for (P, P, P) {
+    fn from(Point3 { x, y, z }: Point3<P>) -> Self {
+        (x, y, z)
+    }
+}
+impl<P> From<[P; 3]> for Point3<P> {
+    fn from([x, y, z]: [P; 3]) -> Self {
+        Self::new(x, y, z)
+    }
+}
+impl<P> Point3<P> {
+    pub fn cast<U>(&self) -> Option<Point3<U>>
+    where
+        P: ToPrimitive + Copy,
+        U: NumCast,
+    {
+        Some(Point3::new(
+            U::from(self.x)?,
+            U::from(self.y)?,
+            U::from(self.z)?,
+        ))
+    }
+    pub fn try_cast<U>(&self) -> Result<Point3<U>>
+    where
+        P: ToPrimitive + Copy,
+        U: NumCast,
+    {
+        Ok(Point3::new(
+            U::from(self.x).ok_or(PointError::conversion("x"))?,
+            U::from(self.y).ok_or(PointError::conversion("y"))?,
+            U::from(self.z).ok_or(PointError::conversion("z"))?,
+        ))
+    }
+}
+
+impl<P: fmt::Display> fmt::Display for Point3<P> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "({}, {}, {})", self.x, self.y, self.z)
+    }
+}
@@ -0,0 +1,16 @@
+use std::fmt;
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+pub enum PolygonError {
+    #[error("Builder error: {0}")]
+    BuilderError(String),
+}
+
+pub type Result<T> = std::result::Result<T, PolygonError>;
+
+impl PolygonError {
+    pub fn builder<M: fmt::Display>(message: M) -> Self {
+        Self::BuilderError(message.to_string())
+    }
+}
@@ -0,0 +1,182 @@
+/// A simple polygon with integer vertices.
+/// Responsibilities:
+/// - Building from vertices or from direction and distance steps
+/// - Shoelace area
+/// - Lattice point counts on the boundary and, by Pick's theorem, the interior
+/// - Winding number containment
+/// - Testing whether a rectangle lies entirely inside
+use super::direction::DirectionBehaviour;
+use super::number_theory::gcd;
+use super::point::Point;
+use num_traits::{PrimInt, Signed};
+
+mod error;
+pub use error::PolygonError;
+use error::Result;
+
+/// A closed polygon; the last vertex joins back to the first.
+/// The boundary is expected not to cross itself.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Polygon<P> {
+    vertices: Vec<Point<P>>,
+}
+
+/// Twice the signed area of the triangle (a, b, c): positive when c is to the left of a → b.
+fn cross<P: PrimInt + Signed>(a: Point<P>, b: Point<P>, c: Point<P>) -> P {
+    (b.x - a.x) * (c.y - a.y) - (b.y - a.y) * (c.x - a.x)
+}
+
+impl<P: PrimInt + Signed> Polygon<P> {
+    pub fn new(vertices: Vec<Point<P>>) -> Result<Self> {
+        if vertices.len() < 3 {
+            return Err(PolygonError::builder(format!(
+                "A polygon needs at least 3 vertices, got {}",
+                vertices.len()
+            )));
+        }
+        Ok(Self { vertices })
+    }
+
+    /// Walks from `start`, moving `distance` in `direction` for each step, with every turn becoming a vertex.
+    /// Fails unless the walk ends back at `start`.
+    pub fn from_steps<D: DirectionBehaviour>(
+        start: Point<P>,
+        steps: impl IntoIterator<Item = (D, P)>,
+    ) -> Result<Self> {
+        let mut vertices = vec![start];
+        let mut current = start;
+        for (direction, distance) in steps {
+            current = current + direction.as_delta::<P>().scale(distance);
+            vertices.push(current);
+        }
+        if current != start {
+            return Err(PolygonError::builder("Steps do not return to the start"));
+        }
+        vertices.pop();
+        Self::new(vertices)
+    }
+
+    pub fn vertices(&self) -> &[Point<P>] {
+        &self.vertices
+    }
+
+    /// Every edge as (start, end), including the one closing the polygon.
+    pub fn edges(&self) -> impl Iterator<Item = (Point<P>, Point<P>)> + '_ {
+        self.vertices
+            .iter()
+            .zip(self.vertices.iter().cycle().skip(1))
+            .map(|(&a, &b)| (a, b))
+    }
+
+    /// Twice the area by the shoelace formula, positive for anticlockwise vertices when x is right and y up.
+    pub fn signed_double_area(&self) -> P {
+        self.edges()
+            .fold(P::zero(), |acc, (a, b)| acc + a.x * b.y - b.x * a.y)
+    }
+
+    /// Twice the area. Always an integer, unlike the area itself.
+    pub fn double_area(&self) -> P {
+        self.signed_double_area().abs()
+    }
+
+    /// The area, rounded down when it is a half.
+    pub fn area(&self) -> P {
+        self.double_area() / (P::one() + P::one())
+    }
+
+    /// Number of lattice points on the boundary.
+    pub fn boundary_points(&self) -> P {
+        self.edges()
+            .fold(P::zero(), |acc, (a, b)| acc + gcd(b.x - a.x, b.y - a.y))
+    }
+
+    /// Number of lattice points strictly inside, by Pick's theorem: A = I + B/2 - 1.
+    pub fn interior_points(&self) -> P {
+        let two = P::one() + P::one();
+        (self.double_area() - self.boundary_points() + two) / two
+    }
+
+    /// Number of lattice points inside or on the boundary, as when counting the cells a dug-out trench encloses.
+    pub fn lattice_points(&self) -> P {
+        self.interior_points() + self.boundary_points()
+    }
+
+    pub fn on_boundary(&self, point: Point<P>) -> bool {
+        self.edges().any(|(a, b)| on_segment(a, b, point))
+    }
+
+    /// How many times the boundary winds anticlockwise around `point`. Zero means outside.
+    /// Points on the boundary give an unspecified value; check `on_boundary` first.
+    pub fn winding_number(&self, point: Point<P>) -> i32 {
+        self.edges().fold(0, |winding, (a, b)| {
+            if a.y <= point.y {
+                if b.y > point.y && cross(a, b, point) > P::zero() {
+                    return winding + 1;
+                }
+            } else if b.y <= point.y && cross(a, b, point) < P::zero() {
+                return winding - 1;
+            }
+            winding
+        })
+    }
+
+    /// Whether `point` is inside or on the boundary.
+    pub fn contains(&self, point: Point<P>) -> bool {
+        self.on_boundary(point) || self.winding_number(point) != 0
+    }
+
+    /// Whether the axis aligned rectangle with opposite corners `a` and `b` lies entirely inside or on the boundary.
+    /// The polygon is treated as a region of the plane, so a rectangle straddling a gap between two edges
+    /// one unit apart is outside even though all of its lattice points are on the boundary.
+    pub fn contains_rectangle(&self, a: Point<P>, b: Point<P>) -> bool {
+        let min = Point::new(a.x.min(b.x), a.y.min(b.y));
+        let max = Point::new(a.x.max(b.x), a.y.max(b.y));
+        let corners = [min, Point::new(min.x, max.y), max, Point::new(max.x, min.y)];
+        if !corners.iter().all(|&corner| self.contains(corner)) {
+            return false;
+        }
+        if min.x == max.x || min.y == max.y {
+            // A degenerate rectangle is a segment, which leaves the polygon only by crossing an edge.
+            return !self
+                .edges()
+                .any(|(start, end)| crosses(start, end, min, max));
+        }
+        // With every corner inside, the rectangle can only poke outside if an edge passes through its interior.
+        !self
+            .edges()
+            .any(|(start, end)| enters_open_box(start, end, min, max))
+    }
+}
+
+fn on_segment<P: PrimInt + Signed>(a: Point<P>, b: Point<P>, point: Point<P>) -> bool {
+    cross(a, b, point) == P::zero()
+        && a.x.min(b.x) <= point.x
+        && point.x <= a.x.max(b.x)
+        && a.y.min(b.y) <= point.y
+        && point.y <= a.y.max(b.y)
+}
+
+/// Whether segments a-b and c-d cross at a single point interior to both.
+fn crosses<P: PrimInt + Signed>(a: Point<P>, b: Point<P>, c: Point<P>, d: Point<P>) -> bool {
+    let opposite =
+        |x: P, y: P| (x > P::zero() && y < P::zero()) || (x < P::zero() && y > P::zero());
+    opposite(cross(a, b, c), cross(a, b, d)) && opposite(cross(c, d, a), cross(c, d, b))
+}
+
+/// Whether segment a-b meets the open interior of the box from `min` to `max`.
+/// By the separating axis theorem it does unless the box's x or y range, or the line through a-b, separates them.
+fn enters_open_box<P: PrimInt + Signed>(
+    a: Point<P>,
+    b: Point<P>,
+    min: Point<P>,
+    max: Point<P>,
+) -> bool {
+    let overlaps_x = a.x.min(b.x) < max.x && min.x < a.x.max(b.x);
+    let overlaps_y = a.y.min(b.y) < max.y && min.y < a.y.max(b.y);
+    if !overlaps_x || !overlaps_y {
+        return false;
+    }
+    let sides = [min, Point::new(min.x, max.y), max, Point::new(max.x, min.y)]
+        .map(|corner| cross(a, b, corner));
+    sides.iter().any(|&side| side > P::zero()) && sides.iter().any(|&side| side < P::zero())
+}
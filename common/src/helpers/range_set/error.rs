@@ -0,0 +1,16 @@
+use std::fmt;
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+pub enum RangeSetError {
+    #[error("Parse error: {0}")]
+    ParseError(String),
+}
+
+pub type Result<T> = std::result::Result<T, RangeSetError>;
+
+impl RangeSetError {
+    pub fn parse<M: fmt::Display>(message: M) -> Self {
+        Self::ParseError(message.to_string())
+    }
+}
@@ -0,0 +1,214 @@
+/// A set of integers stored as disjoint inclusive ranges.
+/// Responsibilities:
+/// - Inserting and removing ranges, merging overlapping and adjacent ones
+/// - Union, intersection and difference with another set
+/// - Containment queries in O(log n)
+/// - Total covered length and the gaps between ranges
+/// - Parsing `a-b` lines
+use num_traits::PrimInt;
+use std::collections::BTreeMap;
+use std::str::FromStr;
+
+mod error;
+pub use error::RangeSetError;
+use error::Result;
+
+/// Ranges are inclusive at both ends and never overlap or touch.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct RangeSet<T> {
+    /// Start of each range mapped to its end.
+    ranges: BTreeMap<T, T>,
+}
+
+impl<T> Default for RangeSet<T> {
+    fn default() -> Self {
+        Self {
+            ranges: BTreeMap::new(),
+        }
+    }
+}
+
+impl<T: PrimInt> RangeSet<T> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds every value from `start` to `end` inclusive, merging with any ranges it overlaps or touches.
+    pub fn insert(&mut self, start: T, end: T) {
+        if start > end {
+            return;
+        }
+        let (mut new_start, mut new_end) = (start, end);
+        let touching: Vec<T> = self
+            .ranges
+            .range(..=end.saturating_add(T::one()))
+            .rev()
+            .take_while(|(_, &e)| e.saturating_add(T::one()) >= start)
+            .map(|(&s, _)| s)
+            .collect();
+        for s in touching {
+            let e = self.ranges.remove(&s).unwrap();
+            new_start = new_start.min(s);
+            new_end = new_end.max(e);
+        }
+        self.ranges.insert(new_start, new_end);
+    }
+
+    /// Removes every value from `start` to `end` inclusive, splitting any range that straddles either end.
+    pub fn remove(&mut self, start: T, end: T) {
+        if start > end {
+            return;
+        }
+        let overlapping: Vec<(T, T)> = self
+            .ranges
+            .range(..=end)
+            .rev()
+            .take_while(|(_, &e)| e >= start)
+            .map(|(&s, &e)| (s, e))
+            .collect();
+        for (s, e) in overlapping {
+            self.ranges.remove(&s);
+            if s < start {
+                self.ranges.insert(s, start - T::one());
+            }
+            if e > end {
+                self.ranges.insert(end + T::one(), e);
+            }
+        }
+    }
+
+    /// Adds every range of `other` to this set.
+    pub fn merge(&mut self, other: &Self) {
+        for (&s, &e) in &other.ranges {
+            self.insert(s, e);
+        }
+    }
+
+    pub fn contains(&self, value: T) -> bool {
+        self.range_containing(value).is_some()
+    }
+
+    /// Whether every value from `start` to `end` inclusive is in the set.
+    pub fn contains_range(&self, start: T, end: T) -> bool {
+        self.range_containing(start).is_some_and(|(_, e)| e >= end)
+    }
+
+    /// The range that `value` falls in, if any.
+    pub fn range_containing(&self, value: T) -> Option<(T, T)> {
+        self.ranges
+            .range(..=value)
+            .next_back()
+            .filter(|(_, &e)| e >= value)
+            .map(|(&s, &e)| (s, e))
+    }
+
+    pub fn union(&self, other: &Self) -> Self {
+        let mut result = self.clone();
+        result.merge(other);
+        result
+    }
+
+    pub fn intersection(&self, other: &Self) -> Self {
+        let mut result = Self::new();
+        let mut lhs = self.iter().peekable();
+        let mut rhs = other.iter().peekable();
+        while let (Some(&(a_start, a_end)), Some(&(b_start, b_end))) = (lhs.peek(), rhs.peek()) {
+            let (start, end) = (a_start.max(b_start), a_end.min(b_end));
+            if start <= end {
+                result.ranges.insert(start, end);
+            }
+            if a_end < b_end {
+                lhs.next();
+            } else {
+                rhs.next();
+            }
+        }
+        result
+    }
+
+    pub fn difference(&self, other: &Self) -> Self {
+        let mut result = self.clone();
+        for (&s, &e) in &other.ranges {
+            result.remove(s, e);
+        }
+        result
+    }
+
+    /// The (start, end) inclusive ranges in ascending order.
+    pub fn iter(&self) -> impl Iterator<Item = (T, T)> + '_ {
+        self.ranges.iter().map(|(&s, &e)| (s, e))
+    }
+
+    /// The (start, end) inclusive ranges missing between the first and last range.
+    pub fn gaps(&self) -> impl Iterator<Item = (T, T)> + '_ {
+        self.iter()
+            .zip(self.iter().skip(1))
+            .map(|((_, end), (start, _))| (end + T::one(), start - T::one()))
+    }
+
+    /// Number of disjoint ranges.
+    pub fn range_count(&self) -> usize {
+        self.ranges.len()
+    }
+
+    /// Total number of values covered, or `None` if the count does not fit in `T`, as for every u64.
+    pub fn len(&self) -> Option<T> {
+        self.iter().try_fold(T::zero(), |total, (s, e)| {
+            total.checked_add(&e.checked_sub(&s)?.checked_add(&T::one())?)
+        })
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.ranges.is_empty()
+    }
+
+    /// The smallest and largest values in the set.
+    pub fn span(&self) -> Option<(T, T)> {
+        let (&start, _) = self.ranges.first_key_value()?;
+        let (_, &end) = self.ranges.last_key_value()?;
+        Some((start, end))
+    }
+}
+
+impl<T: PrimInt + FromStr> RangeSet<T> {
+    /// Parses one `a-b` range per line, skipping blank lines. Negative bounds such as `-5--3` are supported.
+    pub fn parse_lines(input: &str) -> Result<Self> {
+        input
+            .lines()
+            .map(str::trim)
+            .filter(|line| !line.is_empty())
+            .map(|line| {
+                // Skip the first character so a leading minus sign isn't mistaken for the separator.
+                let split = line
+                    .get(1..)
+                    .and_then(|rest| rest.find('-'))
+                    .map(|idx| idx + 1)
+                    .ok_or_else(|| RangeSetError::parse(format!("Missing '-' in {:?}", line)))?;
+                let parse = |s: &str| {
+                    s.trim()
+                        .parse::<T>()
+                        .map_err(|_| RangeSetError::parse(format!("Invalid bound {:?}", s)))
+                };
+                Ok((parse(&line[..split])?, parse(&line[split + 1..])?))
+            })
+            .collect()
+    }
+}
+
+impl<T: PrimInt> FromIterator<(T, T)> for RangeSet<T> {
+    fn from_iter<I: IntoIterator<Item = (T, T)>>(iter: I) -> Self {
+        let mut set = Self::new();
+        for (start, end) in iter {
+            set.insert(start, end);
+        }
+        set
+    }
+}
+
+impl<T: PrimInt + FromStr> FromStr for RangeSet<T> {
+    type Err = RangeSetError;
+
+    fn from_str(s: &str) -> Result<Self> {
+        Self::parse_lines(s)
+    }
+}
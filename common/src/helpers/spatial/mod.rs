@@ -0,0 +1,335 @@
+/// A static k-d tree over 2D and 3D points.
+/// Responsibilities:
+/// - Building a balanced tree over Point, Point3 or fixed size arrays
+/// - k-nearest neighbour, radius and bounding box queries
+/// - Streaming every pair of points in increasing distance order without materialising all n² pairs
+/// - Integer and float coordinates alike, compared by exact squared distance
+use super::point::{Point, Point3};
+use num_traits::Zero;
+use std::cmp::{Ordering, Reverse};
+use std::collections::BinaryHeap;
+use std::ops::{Add, Mul, Sub};
+
+/// A coordinate type distances can be measured in.
+pub trait Coordinate:
+    Copy + PartialOrd + Zero + Add<Output = Self> + Sub<Output = Self> + Mul<Output = Self>
+{
+}
+
+impl<P> Coordinate for P where
+    P: Copy + PartialOrd + Zero + Add<Output = Self> + Sub<Output = Self> + Mul<Output = Self>
+{
+}
+
+/// A point with a fixed number of axes.
+pub trait SpatialPoint: Copy {
+    type Coord: Coordinate;
+    const DIMENSIONS: usize;
+
+    fn coord(&self, axis: usize) -> Self::Coord;
+
+    /// Squared straight line distance, safe for unsigned coordinates.
+    fn squared_distance(&self, other: &Self) -> Self::Coord {
+        (0..Self::DIMENSIONS).fold(Self::Coord::zero(), |acc, axis| {
+            let diff = abs_diff(self.coord(axis), other.coord(axis));
+            acc + diff * diff
+        })
+    }
+}
+
+fn abs_diff<C: Coordinate>(a: C, b: C) -> C {
+    if a > b {
+        a - b
+    } else {
+        b - a
+    }
+}
+
+impl<P: Coordinate> SpatialPoint for Point<P> {
+    type Coord = P;
+    const DIMENSIONS: usize = 2;
+
+    fn coord(&self, axis: usize) -> P {
+        self[axis]
+    }
+}
+
+impl<P: Coordinate> SpatialPoint for Point3<P> {
+    type Coord = P;
+    const DIMENSIONS: usize = 3;
+
+    fn coord(&self, axis: usize) -> P {
+        self[axis]
+    }
+}
+
+impl<P: Coordinate, const N: usize> SpatialPoint for [P; N] {
+    type Coord = P;
+    const DIMENSIONS: usize = N;
+
+    fn coord(&self, axis: usize) -> P {
+        self[axis]
+    }
+}
+
+/// A squared distance with a total order, treating incomparable floats as equal.
+#[derive(Debug, Clone, Copy)]
+struct Ordered<C, T>(C, T);
+
+impl<C: PartialOrd, T: Ord> PartialEq for Ordered<C, T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl<C: PartialOrd, T: Ord> Eq for Ordered<C, T> {}
+
+impl<C: PartialOrd, T: Ord> PartialOrd for Ordered<C, T> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<C: PartialOrd, T: Ord> Ord for Ordered<C, T> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.0
+            .partial_cmp(&other.0)
+            .unwrap_or(Ordering::Equal)
+            .then_with(|| self.1.cmp(&other.1))
+    }
+}
+
+/// A balanced k-d tree. Queries return indices into the points it was built from,
+/// alongside squared distances where relevant.
+#[derive(Debug, Clone)]
+pub struct KdTree<T> {
+    points: Vec<T>,
+    /// Point indices laid out so the median of every range is the node splitting it.
+    order: Vec<usize>,
+}
+
+impl<T: SpatialPoint> KdTree<T> {
+    pub fn new(points: Vec<T>) -> Self {
+        let mut order: Vec<usize> = (0..points.len()).collect();
+        Self::build(&points, &mut order, 0);
+        Self { points, order }
+    }
+
+    fn build(points: &[T], order: &mut [usize], depth: usize) {
+        if order.len() <= 1 {
+            return;
+        }
+        let axis = depth % T::DIMENSIONS;
+        let mid = order.len() / 2;
+        order.select_nth_unstable_by(mid, |&a, &b| {
+            points[a]
+                .coord(axis)
+                .partial_cmp(&points[b].coord(axis))
+                .unwrap_or(Ordering::Equal)
+        });
+        let (left, right) = order.split_at_mut(mid);
+        Self::build(points, left, depth + 1);
+        Self::build(points, &mut right[1..], depth + 1);
+    }
+
+    pub fn len(&self) -> usize {
+        self.points.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.points.is_empty()
+    }
+
+    pub fn points(&self) -> &[T] {
+        &self.points
+    }
+
+    pub fn point(&self, idx: usize) -> &T {
+        &self.points[idx]
+    }
+
+    /// The `k` points closest to `query` as (index, squared distance), closest first.
+    pub fn nearest(&self, query: &T, k: usize) -> Vec<(usize, T::Coord)> {
+        self.nearest_matching(query, k, |_| true)
+    }
+
+    /// The `k` points closest to `query` among those whose index satisfies `keep`, closest first.
+    pub fn nearest_matching(
+        &self,
+        query: &T,
+        k: usize,
+        keep: impl Fn(usize) -> bool,
+    ) -> Vec<(usize, T::Coord)> {
+        let mut best = BinaryHeap::with_capacity(k + 1);
+        if k > 0 {
+            self.nearest_in((0, self.order.len()), 0, query, k, &keep, &mut best);
+        }
+        best.into_sorted_vec()
+            .into_iter()
+            .map(|Ordered(distance, idx)| (idx, distance))
+            .collect()
+    }
+
+    fn nearest_in(
+        &self,
+        (lo, hi): (usize, usize),
+        depth: usize,
+        query: &T,
+        k: usize,
+        keep: &impl Fn(usize) -> bool,
+        best: &mut BinaryHeap<Ordered<T::Coord, usize>>,
+    ) {
+        if lo >= hi {
+            return;
+        }
+        let mid = (lo + hi) / 2;
+        let idx = self.order[mid];
+        let point = &self.points[idx];
+        if keep(idx) {
+            best.push(Ordered(point.squared_distance(query), idx));
+            if best.len() > k {
+                best.pop();
+            }
+        }
+
+        let axis = depth % T::DIMENSIONS;
+        let split = abs_diff(query.coord(axis), point.coord(axis));
+        let (near, far) = if query.coord(axis) < point.coord(axis) {
+            ((lo, mid), (mid + 1, hi))
+        } else {
+            ((mid + 1, hi), (lo, mid))
+        };
+        self.nearest_in(near, depth + 1, query, k, keep, best);
+        let worst = best.peek().map(|Ordered(distance, _)| *distance);
+        if best.len() < k || worst.is_some_and(|worst| split * split <= worst) {
+            self.nearest_in(far, depth + 1, query, k, keep, best);
+        }
+    }
+
+    /// Every point within `radius` of `query`, inclusive, as (index, squared distance) in no particular order.
+    pub fn within_radius(&self, query: &T, radius: T::Coord) -> Vec<(usize, T::Coord)> {
+        let limit = radius * radius;
+        let mut found = Vec::new();
+        self.visit(0, self.order.len(), 0, &mut |idx, depth| {
+            let point = &self.points[idx];
+            let distance = point.squared_distance(query);
+            if distance <= limit {
+                found.push((idx, distance));
+            }
+            let axis = depth % T::DIMENSIONS;
+            let (q, p) = (query.coord(axis), point.coord(axis));
+            let split = abs_diff(q, p) * abs_diff(q, p);
+            // Which sides of the split could still hold points in range.
+            (q < p || split <= limit, q >= p || split <= limit)
+        });
+        found
+    }
+
+    /// Indices of every point inside the box between `min` and `max`, inclusive on every axis.
+    pub fn in_box(&self, min: &T, max: &T) -> Vec<usize> {
+        let mut found = Vec::new();
+        self.visit(0, self.order.len(), 0, &mut |idx, depth| {
+            let point = &self.points[idx];
+            let inside = (0..T::DIMENSIONS).all(|axis| {
+                min.coord(axis) <= point.coord(axis) && point.coord(axis) <= max.coord(axis)
+            });
+            if inside {
+                found.push(idx);
+            }
+            let axis = depth % T::DIMENSIONS;
+            (
+                min.coord(axis) <= point.coord(axis),
+                point.coord(axis) <= max.coord(axis),
+            )
+        });
+        found
+    }
+
+    /// Visits nodes, with `visit` returning whether to descend to the (lower, upper) sides of each split.
+    fn visit(
+        &self,
+        lo: usize,
+        hi: usize,
+        depth: usize,
+        visit: &mut impl FnMut(usize, usize) -> (bool, bool),
+    ) {
+        if lo >= hi {
+            return;
+        }
+        let mid = (lo + hi) / 2;
+        let (lower, upper) = visit(self.order[mid], depth);
+        if lower {
+            self.visit(lo, mid, depth + 1, visit);
+        }
+        if upper {
+            self.visit(mid + 1, hi, depth + 1, visit);
+        }
+    }
+
+    /// Every pair of distinct points as (squared distance, lower index, higher index), closest pairs first.
+    /// Neighbours are fetched lazily per point, so taking the first few pairs stays cheap.
+    pub fn closest_pairs(&self) -> ClosestPairs<'_, T> {
+        let mut pairs = ClosestPairs {
+            tree: self,
+            cursors: vec![PairCursor::default(); self.len()],
+            heap: BinaryHeap::new(),
+        };
+        (0..self.len()).for_each(|idx| pairs.advance(idx));
+        pairs
+    }
+}
+
+#[derive(Debug, Clone)]
+struct PairCursor<C> {
+    /// Nearest higher-indexed neighbours fetched so far, closest first.
+    fetched: Vec<(usize, C)>,
+    next: usize,
+    exhausted: bool,
+}
+
+impl<C> Default for PairCursor<C> {
+    fn default() -> Self {
+        Self {
+            fetched: Vec::new(),
+            next: 0,
+            exhausted: false,
+        }
+    }
+}
+
+/// A candidate pair, ordered by distance then by indices.
+type PairEntry<C> = Ordered<C, (usize, usize)>;
+
+/// Iterator over pairs of points in increasing distance order. See [`KdTree::closest_pairs`].
+pub struct ClosestPairs<'a, T: SpatialPoint> {
+    tree: &'a KdTree<T>,
+    cursors: Vec<PairCursor<T::Coord>>,
+    heap: BinaryHeap<Reverse<PairEntry<T::Coord>>>,
+}
+
+impl<T: SpatialPoint> ClosestPairs<'_, T> {
+    /// Queues the next closest partner of `idx`, fetching twice as many neighbours when the cache runs out.
+    fn advance(&mut self, idx: usize) {
+        let cursor = &mut self.cursors[idx];
+        if cursor.next == cursor.fetched.len() && !cursor.exhausted {
+            let want = (cursor.fetched.len() * 2).max(4);
+            let query = self.tree.point(idx);
+            cursor.fetched = self.tree.nearest_matching(query, want, |other| other > idx);
+            cursor.exhausted = cursor.fetched.len() < want;
+        }
+        if let Some(&(other, distance)) = cursor.fetched.get(cursor.next) {
+            cursor.next += 1;
+            self.heap.push(Reverse(Ordered(distance, (idx, other))));
+        }
+    }
+}
+
+impl<T: SpatialPoint> Iterator for ClosestPairs<'_, T> {
+    type Item = (T::Coord, usize, usize);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let Reverse(Ordered(distance, (a, b))) = self.heap.pop()?;
+        self.advance(a);
+        Some((distance, a, b))
+    }
+}
@@ -0,0 +1,132 @@
+/// A disjoint-set forest over the indices 0..n.
+/// Responsibilities:
+/// - Union by rank with path compression
+/// - Component sizes, counts and enumeration
+/// - Kruskal's minimum spanning tree
+use std::collections::HashMap;
+
+#[derive(Debug, Clone)]
+pub struct UnionFind {
+    parent: Vec<usize>,
+    rank: Vec<u8>,
+    size: Vec<usize>,
+    components: usize,
+}
+
+impl UnionFind {
+    pub fn new(size: usize) -> Self {
+        Self {
+            parent: (0..size).collect(),
+            rank: vec![0; size],
+            size: vec![1; size],
+            components: size,
+        }
+    }
+
+    /// Number of elements.
+    pub fn len(&self) -> usize {
+        self.parent.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.parent.is_empty()
+    }
+
+    /// The representative of the set containing `x`, compressing the path along the way.
+    pub fn find(&mut self, x: usize) -> usize {
+        let mut root = x;
+        while self.parent[root] != root {
+            root = self.parent[root];
+        }
+        let mut current = x;
+        while self.parent[current] != root {
+            current = std::mem::replace(&mut self.parent[current], root);
+        }
+        root
+    }
+
+    /// Unites the sets containing `x` and `y`.
+    ///
+    /// Returns `true` if the sets were separate and have now been merged,
+    /// or `false` if `x` and `y` were already in the same set.
+    pub fn union(&mut self, x: usize, y: usize) -> bool {
+        let mut root_x = self.find(x);
+        let mut root_y = self.find(y);
+
+        if root_x == root_y {
+            return false;
+        }
+        if self.rank[root_x] < self.rank[root_y] {
+            std::mem::swap(&mut root_x, &mut root_y);
+        }
+        self.parent[root_y] = root_x;
+        self.size[root_x] += self.size[root_y];
+        if self.rank[root_x] == self.rank[root_y] {
+            self.rank[root_x] += 1;
+        }
+        self.components -= 1;
+        true
+    }
+
+    pub fn connected(&mut self, x: usize, y: usize) -> bool {
+        self.find(x) == self.find(y)
+    }
+
+    /// Number of elements in the set containing `x`.
+    pub fn component_size(&mut self, x: usize) -> usize {
+        let root = self.find(x);
+        self.size[root]
+    }
+
+    /// Number of disjoint sets.
+    pub fn component_count(&self) -> usize {
+        self.components
+    }
+
+    /// The members of every set, in no particular order.
+    pub fn groups(&mut self) -> Vec<Vec<usize>> {
+        let mut map: HashMap<usize, Vec<usize>> = HashMap::new();
+        for idx in 0..self.parent.len() {
+            let root = self.find(idx);
+            map.entry(root).or_default().push(idx);
+        }
+        map.into_values().collect()
+    }
+
+    /// Sizes of every set, largest first.
+    pub fn component_sizes(&self) -> Vec<usize> {
+        let mut sizes: Vec<usize> = (0..self.parent.len())
+            .filter(|&idx| self.parent[idx] == idx)
+            .map(|idx| self.size[idx])
+            .collect();
+        sizes.sort_unstable_by(|a, b| b.cmp(a));
+        sizes
+    }
+}
+
+/// Kruskal's algorithm over `nodes` nodes and `(weight, a, b)` edges.
+/// Returns the edges of a minimum spanning tree in the order they were added, so the last edge is the one that
+/// finally connects everything, or `None` when the edges leave the nodes in more than one component.
+/// Weights must be totally ordered; compare Euclidean distances squared so they stay integers.
+pub fn minimum_spanning_tree<W>(
+    nodes: usize,
+    edges: impl IntoIterator<Item = (W, usize, usize)>,
+) -> Option<Vec<(W, usize, usize)>>
+where
+    W: Ord,
+{
+    let mut edges: Vec<_> = edges.into_iter().collect();
+    edges.sort_by(|lhs, rhs| lhs.0.cmp(&rhs.0));
+
+    let mut uf = UnionFind::new(nodes);
+    let mut tree = Vec::with_capacity(nodes.saturating_sub(1));
+    for (weight, a, b) in edges {
+        if uf.union(a, b) {
+            tree.push((weight, a, b));
+            if uf.component_count() == 1 {
+                break;
+            }
+        }
+    }
+    (uf.component_count() <= 1).then_some(tree)
+}
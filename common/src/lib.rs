@@ -1,16 +1,38 @@
 /// Core abstractions:
 ///     - Point<P> -> Represents a location in 2D space
-///     - Direction -> Represents movement vectors (Cardinal/Octal)
+///     - Point3<P> -> Represents a location in 3D space
+///     - Hex<P> -> Represents a hexagonal cell in axial coordinates
+///     - Direction -> Represents movement vectors (Cardinal/Octal/Hex)
 ///     - Grid<T> -> Represents a bounded 2D space containing values
 mod helpers;
 mod macros;
 pub mod prelude {
     pub use crate::helpers::{
+        cycle::{Cycle, CycleHistory},
         direction::{
-            CardinalDirections, DiagonalDirections, DirectionBehaviour, DirectionalMove,
-            OctalDirections, RotationBehaviour,
+            CardinalDirections, DiagonalDirections, DirectionBehaviour, DirectionError,
+            DirectionalMove, HexDirections, HexLayout, OctalDirections, RotationBehaviour,
+            TurnInstruction, TurnInstructions,
         },
-        grid::{BoolGrid, CharGrid, Grid, GridPoint, GridView, IntGrid, Rectangle, WorldPoint},
-        point::Point,
+        graph::{Graph, GraphError, Interner},
+        grid::{
+            Automaton, BitGrid, BoolGrid, CellView, CharGrid, CompressedAxis, CompressedGrid, Grid,
+            Grid3, GridPoint, GridPoint3, GridView, HexGrid, HexPoint, IntGrid, Neighbourhood,
+            Overlap, Ray, Rectangle, Region, Regions, SearchResult, SparseGrid, SparseSearchResult,
+            Stencil, SummedArea, Transform, WordMatch, WorldPoint, WrappingGrid,
+        },
+        line::{bresenham, Bresenham, Intersection, Segment},
+        linear::{
+            BitVector, Gf2Solution, Gf2System, LinearError, LinearSolution, LinearSystem, Rational,
+        },
+        number_theory::{
+            crt, extended_gcd, gcd, gcd_all, lcm, lcm_all, mod_inverse, mod_pow, modulo, mul_mod,
+            DynModInt, ModInt, Sieve,
+        },
+        point::{Connectivity3, Hex, Point, Point3},
+        polygon::{Polygon, PolygonError},
+        range_set::{RangeSet, RangeSetError},
+        spatial::{ClosestPairs, Coordinate, KdTree, SpatialPoint},
+        union_find::{minimum_spanning_tree, UnionFind},
     };
 }